@@ -0,0 +1,101 @@
+use std::slice;
+
+use crate::{Pin, Rule, History};
+use crate::code::Code;
+use crate::solver::{self, Solver};
+
+// 回答で候補がどう分かれるか
+pub struct Partition {
+    pub worst: usize,   // 最悪の場合に残る候補数
+    pub parts: usize,   // 分かれる数
+    pub entropy: f64,   // 得られる情報量の期待値(bit)
+}
+
+impl Partition {
+
+    fn new(guess: Code, candidates: &[Code]) -> Self {
+        let sizes = solver::partition(guess, candidates);
+        let total = candidates.len() as f64;
+        Self {
+            worst: sizes.iter().max().copied().unwrap_or(0),
+            parts: sizes.iter().filter(|size| **size > 0).count(),
+            entropy: sizes.iter().filter(|size| **size > 0)
+                .map(|size| {
+                    let p = *size as f64 / total;
+                    -p * p.log2()
+                })
+                .sum(),
+        }
+    }
+}
+
+// 1回の回答の分析
+pub struct Analysis {
+    pub before: usize,  // 回答前の候補数
+    pub after: usize,   // 回答後の候補数
+    pub guess: Partition,
+    pub best: Vec<Pin>,         // 同じ状況でソルバーが選ぶ回答
+    pub best_partition: Partition,
+}
+
+impl Analysis {
+
+    // 実際に得られた情報量(bit)
+    pub fn bits(&self) -> f64 {
+        (self.before as f64 / self.after.max(1) as f64).log2()
+    }
+}
+
+// 履歴を最初からたどって、回答ごとに分析する
+// 候補は回答ごとに絞っていく。ルールにない色の回答があれば、そこまで
+pub fn analyze(rule: &Rule, histories: &[History], solver: &mut dyn Solver) -> Vec<Analysis> {
+    let palette = rule.palette();
+    let mut candidates: Vec<Code> = rule.code_space().iter().collect();
+    let mut analyses = Vec::new();
+    for (i, history) in histories.iter().enumerate() {
+        let best = solver.next_guess(&histories[..i]).unwrap_or_else(|| history.pins.clone());
+        let (Some(guess), Some(best_code)) = (Code::from_pins(&history.pins, &palette), Code::from_pins(&best, &palette)) else { break };
+        let after = solver::candidates(&candidates, &palette, slice::from_ref(history));
+        analyses.push(Analysis {
+            before: candidates.len(),
+            after: after.len(),
+            guess: Partition::new(guess, &candidates),
+            best_partition: Partition::new(best_code, &candidates),
+            best,
+        });
+        candidates = after;
+    }
+    analyses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{histories, remaining_counts};
+    use crate::solver::{PartitionSolver, Strategy};
+
+    #[test]
+    fn analyze_histories() {
+        let rule = Rule::default();
+        let answer = vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let histories = histories(&answer, vec![
+            vec![ Pin::pink(), Pin::orange(), Pin::red(), Pin::blue() ],
+            vec![ Pin::pink(), Pin::orange(), Pin::green(), Pin::yellow() ],
+            vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ],
+        ]);
+
+        let mut solver = PartitionSolver::new(&rule, Strategy::Minimax);
+        let analyses = analyze(&rule, &histories, &mut solver);
+        let remainings = remaining_counts(&rule, &histories);
+
+        assert_eq!(analyses.len(), 3);
+        assert_eq!(analyses[0].before, 360);
+        for (analysis, remaining) in analyses.iter().zip(&remainings) {
+            assert_eq!(analysis.after, *remaining);
+            assert!(analysis.best_partition.worst <= analysis.guess.worst);
+        }
+        assert!((analyses[0].bits() - (360.0f64 / 84.0).log2()).abs() < 1e-9);
+        // 最後は答えそのもの
+        assert_eq!(analyses[2].after, 1);
+    }
+}
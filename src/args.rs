@@ -0,0 +1,245 @@
+use crate::{Error, Rule};
+use crate::console_view::Confirm;
+use crate::puzzle::Difficulty;
+use crate::solver::{self, Solver};
+use crate::table::FeedbackTable;
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    Play,   // ゲームをする
+    Solve,  // ソルバーに解かせる
+    Bench,  // ソルバーにたくさん解かせて集計する
+    Breaker,    // プレイヤーが答えを考え、コンピュータが当てる
+    Assist,     // ボードゲームの補助
+    Book,       // ソルバーの定跡を作る
+    Tree,       // ソルバーの決定木を出力する
+    Replay,     // 記録したゲームを再生する
+    Stats,      // 成績を見る
+    Export,     // 記録したゲームを出力する
+    ExportStats,    // 成績を出力する
+    Puzzle,     // 推理パズル
+    Puzzles,    // 推理パズルをまとめて作る
+}
+
+// コマンドライン引数
+//  master-mind [solve|bench|breaker|assist|book|tree|replay|stats|export|export-stats|puzzle|puzzles] [--rule <名前>] [--solver <名前>] [--seed <数値>] [--remaining]
+//              [--threads <数値>] [--sample <数値>] [--format <json|dot|csv|text>] [--resume]
+//              [--record <パス>] [--share <パス>] [--output <パス>] [--puzzle <パス>]
+//              [--number <数値>] [--difficulty <easy|normal|hard|expert>] [--count <数値>] [--practice]
+//              [--confirm <always|off|contradiction|quick>]
+#[derive(Debug)]
+pub struct Args {
+    pub command: Command,
+    pub rule: String,
+    pub solver: String,
+    pub seed: Option<u64>,
+    pub remaining: bool,        // 残っている答えの数をゲーム中に表示する
+    pub threads: Option<usize>, // ソルバーが使うスレッド数(指定がなければ CPU の数)
+    pub sample: Option<usize>,  // bench で解かせる答えの数(指定がなければすべて)
+    pub format: Option<String>, // tree, export, bench の出力形式(指定がなければ tree, export は json、bench は text)
+    pub resume: bool,           // 中断したゲームを続ける
+    pub record: Option<String>, // replay で再生する記録のファイル(指定がなければ最後のゲーム)
+    pub share: Option<String>,  // 結果を共有する文章を書くファイル(指定がなければ標準出力)
+    pub output: Option<String>, // export, puzzles, tree の出力先のファイル(指定がなければ標準出力)、puzzle で作ったパズルを書くファイル
+    pub puzzle: Option<String>, // puzzle で解くパズルのファイル(指定がなければ作る)
+    pub number: usize,          // パズルのファイルの何番目 (1 から) を解くか
+    pub difficulty: Option<Difficulty>, // 作るパズルの難しさ
+    pub count: usize,           // puzzles で難しさごとに作るパズルの数
+    pub practice: bool,         // 練習: 最後の回答を取り消せる(取り消したゲームはランク外)
+    pub confirm: Confirm,       // 回答を決めるときの確認
+}
+
+impl Args {
+
+    pub fn parse(args: impl Iterator<Item = String>) -> crate::Result<Args> {
+        let mut parsed = Args {
+            command: Command::Play, rule: String::from("default"), solver: String::from("minimax"), seed: None,
+            remaining: false, threads: None, sample: None, format: None,
+            resume: false, record: None, share: None, output: None, puzzle: None,
+            number: 1, difficulty: None, count: 5, practice: false,
+            confirm: Confirm::Always,
+        };
+
+        let mut args = args.peekable();
+        if let Some(command) = args.next_if(|arg| !arg.starts_with("--")) {
+            parsed.command = match command.as_str() {
+                "solve" => Command::Solve,
+                "bench" => Command::Bench,
+                "breaker" => Command::Breaker,
+                "assist" => Command::Assist,
+                "book" => Command::Book,
+                "tree" => Command::Tree,
+                "replay" => Command::Replay,
+                "stats" => Command::Stats,
+                "export" => Command::Export,
+                "export-stats" => Command::ExportStats,
+                "puzzle" => Command::Puzzle,
+                "puzzles" => Command::Puzzles,
+                _ => return Err(Box::new(Error::Args { arg: command })),
+            };
+        }
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(Error::Args { arg: arg.clone() });
+            match arg.as_str() {
+                "--rule" => {
+                    let name = value()?;
+                    if !crate::RULES.contains(&name.as_str()) {
+                        return Err(Box::new(Error::Args { arg: name }));
+                    }
+                    parsed.rule = name;
+                },
+                "--solver" => {
+                    let name = value()?;
+                    if !solver::NAMES.contains(&name.as_str()) {
+                        return Err(Box::new(Error::Args { arg: name }));
+                    }
+                    parsed.solver = name;
+                },
+                "--seed" => {
+                    let seed = value()?;
+                    parsed.seed = Some(seed.parse().map_err(|_| Error::Args { arg: seed })?);
+                },
+                "--remaining" => parsed.remaining = true,
+                "--threads" => {
+                    let threads = value()?;
+                    match threads.parse() {
+                        Ok(threads) if threads > 0 => parsed.threads = Some(threads),
+                        _ => return Err(Box::new(Error::Args { arg: threads })),
+                    }
+                },
+                "--sample" => {
+                    let sample = value()?;
+                    parsed.sample = Some(sample.parse().map_err(|_| Error::Args { arg: sample })?);
+                },
+                "--resume" => parsed.resume = true,
+                "--practice" => parsed.practice = true,
+                "--confirm" => {
+                    let name = value()?;
+                    parsed.confirm = Confirm::from_name(&name).ok_or(Error::Args { arg: name })?;
+                },
+                "--record" => parsed.record = Some(value()?),
+                "--share" => parsed.share = Some(value()?),
+                "--output" => parsed.output = Some(value()?),
+                "--puzzle" => parsed.puzzle = Some(value()?),
+                "--number" => {
+                    let number = value()?;
+                    match number.parse() {
+                        Ok(number) if number > 0 => parsed.number = number,
+                        _ => return Err(Box::new(Error::Args { arg: number })),
+                    }
+                },
+                "--difficulty" => {
+                    let name = value()?;
+                    parsed.difficulty = Some(Difficulty::from_name(&name).ok_or(Error::Args { arg: name })?);
+                },
+                "--count" => {
+                    let count = value()?;
+                    parsed.count = count.parse().map_err(|_| Error::Args { arg: count })?;
+                },
+                "--format" => {
+                    let format = value()?;
+                    // 使えるかどうかはコマンドごとに調べる
+                    let formats = [ &crate::tree::FORMATS[..], &crate::export::FORMATS, &crate::bench::FORMATS ];
+                    if !formats.concat().contains(&format.as_str()) {
+                        return Err(Box::new(Error::Args { arg: format }));
+                    }
+                    parsed.format = Some(format);
+                },
+                _ => return Err(Box::new(Error::Args { arg })),
+            }
+        }
+        Ok(parsed)
+    }
+
+    pub fn rule(&self) -> crate::Result<Rule> {
+        Ok(Rule::preset(&self.rule).ok_or(Error::Args { arg: self.rule.clone() })?)
+    }
+
+    pub fn solver(&self, rule: &Rule, seed: u64) -> crate::Result<Box<dyn Solver>> {
+        let mut solver = solver::by_name(&self.solver, rule, seed).ok_or(Error::Args { arg: self.solver.clone() })?;
+        if let Some(threads) = self.threads {
+            solver.set_threads(threads);
+        }
+        // ランダムなソルバーはヒントの表を使わないので作らない
+        if self.solver != "random" {
+            if let Some(table) = FeedbackTable::load_or_new(rule) {
+                solver.set_table(table);
+            }
+        }
+        Ok(solver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> crate::Result<Args> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn args_parse() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.command, Command::Play);
+        assert_eq!(args.solver, "minimax");
+        assert_eq!(args.seed, None);
+        assert!(!args.remaining);
+        assert!(!args.resume);
+
+        let args = parse(&[ "solve", "--solver", "entropy", "--seed", "42", "--remaining" ]).unwrap();
+        assert_eq!(args.command, Command::Solve);
+        assert_eq!(args.solver, "entropy");
+        assert_eq!(args.seed, Some(42));
+        assert!(args.remaining);
+
+        let args = parse(&[ "bench", "--rule", "classic", "--threads", "2", "--sample", "100", "--format", "csv" ]).unwrap();
+        assert_eq!(args.command, Command::Bench);
+        assert_eq!(args.threads, Some(2));
+        assert_eq!(args.rule, "classic");
+        assert_eq!(args.sample, Some(100));
+        assert_eq!(args.format.as_deref(), Some("csv"));
+        assert!(parse(&[ "bench", "--csv" ]).is_err());
+
+        assert!(parse(&[ "--resume" ]).unwrap().resume);
+        assert!(parse(&[ "--practice" ]).unwrap().practice);
+        assert_eq!(parse(&[]).unwrap().confirm, Confirm::Always);
+        assert_eq!(parse(&[ "--confirm", "quick" ]).unwrap().confirm, Confirm::Quick);
+        assert!(parse(&[ "--confirm", "never" ]).is_err());
+        assert_eq!(parse(&[ "--share", "result.txt" ]).unwrap().share.as_deref(), Some("result.txt"));
+        let args = parse(&[ "replay", "--record", "game.txt" ]).unwrap();
+        assert_eq!(args.command, Command::Replay);
+        assert_eq!(args.record.as_deref(), Some("game.txt"));
+        assert_eq!(parse(&[ "stats" ]).unwrap().command, Command::Stats);
+        let args = parse(&[ "export-stats", "--format", "csv", "--output", "stats.csv" ]).unwrap();
+        assert_eq!(args.command, Command::ExportStats);
+        assert_eq!(args.format.as_deref(), Some("csv"));
+        assert_eq!(args.output.as_deref(), Some("stats.csv"));
+        let args = parse(&[ "puzzle", "--puzzle", "p.txt" ]).unwrap();
+        assert_eq!(args.command, Command::Puzzle);
+        assert_eq!(args.puzzle.as_deref(), Some("p.txt"));
+        assert_eq!(args.number, 1);
+        let args = parse(&[ "puzzles", "--difficulty", "expert", "--count", "3" ]).unwrap();
+        assert_eq!(args.command, Command::Puzzles);
+        assert_eq!(args.difficulty, Some(Difficulty::Expert));
+        assert_eq!(args.count, 3);
+        assert!(parse(&[ "--difficulty", "impossible" ]).is_err());
+        assert!(parse(&[ "--number", "0" ]).is_err());
+        assert_eq!(parse(&[ "breaker" ]).unwrap().command, Command::Breaker);
+        assert_eq!(parse(&[ "assist" ]).unwrap().command, Command::Assist);
+        assert_eq!(parse(&[ "book" ]).unwrap().command, Command::Book);
+
+        let args = parse(&[ "tree", "--format", "dot" ]).unwrap();
+        assert_eq!(args.command, Command::Tree);
+        assert_eq!(args.format.as_deref(), Some("dot"));
+        assert!(parse(&[ "tree", "--format", "xml" ]).is_err());
+        assert!(parse(&[ "unknown" ]).is_err());
+        assert!(parse(&[ "--rule", "unknown" ]).is_err());
+        assert!(parse(&[ "--threads", "0" ]).is_err());
+        assert!(parse(&[ "--solver", "unknown" ]).is_err());
+        assert!(parse(&[ "--seed" ]).is_err());
+        assert!(parse(&[ "--seed", "abc" ]).is_err());
+        assert!(parse(&[ "--unknown" ]).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::{Args, Error, History, Pin, hints};
+use crate::code::Code;
+use crate::solver::Solver;
+
+// 出力できる形式
+pub const FORMATS: [&str; 2] = [ "text", "csv" ];
+
+// answer をソルバーに当てさせて、かかった回数を返す。try_count 回で当てられなければ None
+pub fn play(solver: &mut dyn Solver, palette: &[Pin], answer: Code, try_count: u32) -> Option<usize> {
+    let mut histories = Vec::new();
+    while histories.len() < try_count as usize {
+        let pins = solver.next_guess(&histories)?;
+        let (hit, blow) = Code::from_pins(&pins, palette)?.hit_blow(answer);
+        histories.push(History { hints: hints(hit, blow, pins.len()), pins });
+        if hit == answer.len() { return Some(histories.len()); }
+    }
+    None
+}
+
+// 集計結果
+pub struct Report {
+    histogram: Vec<usize>,  // 当てた回数ごとのゲーム数 (histogram[0] が 1 回)
+    failures: usize,        // try_count 回で当てられなかったゲーム数
+    elapsed: Duration,
+}
+
+impl Report {
+
+    fn games(&self) -> usize {
+        self.histogram.iter().sum::<usize>() + self.failures
+    }
+
+    // 当てられたゲームの平均回数
+    fn average(&self) -> f64 {
+        let solved: usize = self.histogram.iter().sum();
+        if solved == 0 { return 0.0; }
+        self.histogram.iter().enumerate().map(|(i, count)| (i + 1) * count).sum::<usize>() as f64 / solved as f64
+    }
+
+    fn worst(&self) -> Option<usize> {
+        self.histogram.iter().rposition(|count| *count > 0).map(|i| i + 1)
+    }
+}
+
+pub fn bench(solver: &mut dyn Solver, palette: &[Pin], answers: &[Code], try_count: u32) -> Report {
+    let started = Instant::now();
+    let mut report = Report { histogram: vec![0; try_count as usize], failures: 0, elapsed: Duration::ZERO };
+    for answer in answers {
+        match play(solver, palette, *answer, try_count) {
+            Some(count) => report.histogram[count - 1] += 1,
+            None => report.failures += 1,
+        }
+    }
+    report.elapsed = started.elapsed();
+    report
+}
+
+pub fn run(args: &Args) -> crate::Result<()> {
+    let format = args.format.as_deref().unwrap_or("text");
+    if !FORMATS.contains(&format) {
+        return Err(Box::new(Error::Args { arg: format.to_string() }));
+    }
+    let rule = args.rule()?;
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // すべての答え、または指定された数だけランダムに選んだ答え
+    let mut answers: Vec<Code> = rule.code_space().iter().collect();
+    if let Some(sample) = args.sample {
+        answers = answers.choose_multiple(&mut rng, sample).copied().collect();
+    }
+    let mut solver = args.solver(&rule, rng.gen())?;
+    let report = bench(solver.as_mut(), &rule.palette(), &answers, rule.try_count);
+
+    let worst = report.worst().map(|worst| worst.to_string()).unwrap_or_default();
+    if format == "csv" {
+        println!("rule,solver,seed,games,average,worst,failures,seconds,{}", (1..=rule.try_count).join(","));
+        println!("{},{},{},{},{:.4},{},{},{:.3},{}",
+            args.rule, args.solver, seed, report.games(), report.average(), worst, report.failures,
+            report.elapsed.as_secs_f64(), report.histogram.iter().join(","));
+    } else {
+        println!("rule: {}  solver: {}  seed: {}  games: {}", args.rule, args.solver, seed, report.games());
+        let max = report.histogram.iter().max().copied().unwrap_or(0).max(1);
+        for (i, count) in report.histogram.iter().enumerate() {
+            println!("{:>3}: {:>7} {}", i + 1, count, "#".repeat((count * 50).div_ceil(max)));
+        }
+        println!("平均: {:.4}  最悪: {}  失敗: {}  時間: {:.3}s", report.average(), worst, report.failures, report.elapsed.as_secs_f64());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rule;
+    use crate::solver;
+
+    #[test]
+    fn bench_report() {
+        let rule = Rule::default();
+        let mut solver = solver::by_name("minimax", &rule, 0).unwrap();
+        let answers: Vec<Code> = rule.code_space().iter().collect();
+        let report = bench(solver.as_mut(), &rule.palette(), &answers[..20], 3);
+
+        assert_eq!(report.games(), 20);
+        assert_eq!(report.histogram.len(), 3);
+        assert_eq!(report.histogram.iter().sum::<usize>() + report.failures, 20);
+        assert!(report.worst().is_none_or(|worst| worst <= 3));
+        assert!(report.average() <= 3.0);
+    }
+}
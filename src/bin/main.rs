@@ -1,5 +1,5 @@
-use master_mind;
-
 fn main() {
-    let result = master_mind::start();
+    if let Err(e) = master_mind::start(std::env::args().skip(1)) {
+        eprintln!("{}", e);
+    }
 }
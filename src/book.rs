@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{Args, Code, Error, History, Rule, hints};
+use crate::solver::{PartitionSolver, Solver, Strategy};
+use crate::table::FeedbackTable;
+
+// 定跡: ルールと戦略ごとの、最初の回答と、そのヒントごとの 2 回目の回答
+// book.txt は `master-mind book > src/book.txt` で作り直す
+const BOOK: &str = include_str!("book.txt");
+
+pub struct Book {
+    first: usize,                               // 最初の回答 (CodeSpace の番号)
+    seconds: BTreeMap<(usize, usize), usize>,   // 最初の回答の (Hit 数, Blow 数) → 2 回目の回答
+}
+
+impl Book {
+
+    // 埋め込んだ定跡から、ルールと戦略に合うものを探す
+    pub fn find(rule: &Rule, strategy: Strategy) -> Option<Self> {
+        Self::parse(BOOK, &rule.code_space().key(), strategy.name())
+    }
+
+    // 1 行は "ルール 戦略 ヒント 回答の番号"。ヒントは "Hit数-Blow数" で、最初の回答は "-"
+    fn parse(text: &str, key: &str, name: &str) -> Option<Self> {
+        let mut first = None;
+        let mut seconds = BTreeMap::new();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [ k, n, feedback, guess ] = fields[..] else { continue };
+            if k != key || n != name { continue; }
+            let guess = guess.parse().ok()?;
+            if feedback == "-" {
+                first = Some(guess);
+            } else {
+                let (hit, blow) = feedback.split_once('-')?;
+                seconds.insert((hit.parse().ok()?, blow.parse().ok()?), guess);
+            }
+        }
+        Some(Self { first: first?, seconds })
+    }
+
+    pub fn first(&self) -> usize {
+        self.first
+    }
+
+    pub fn second(&self, hit: usize, blow: usize) -> Option<usize> {
+        self.seconds.get(&(hit, blow)).copied()
+    }
+
+    // 定跡を使わないソルバーに解かせて作る。table があればソルバーに使わせる
+    pub fn generate(rule: &Rule, strategy: Strategy, threads: Option<usize>, table: Option<FeedbackTable>) -> Option<Self> {
+        let space = rule.code_space();
+        let palette = rule.palette();
+        let len = rule.answer_count as usize;
+        let mut solver = PartitionSolver::new(rule, strategy);
+        solver.set_book(None);
+        if let Some(threads) = threads {
+            solver.set_threads(threads);
+        }
+        if let Some(table) = table {
+            solver.set_table(table);
+        }
+
+        let pins = solver.next_guess(&[])?;
+        let first = Code::from_pins(&pins, &palette)?;
+        // 当たり以外で、最初の回答に返ってくるヒントごと
+        let feedbacks: BTreeSet<(usize, usize)> = space.iter()
+            .map(|answer| first.hit_blow(answer))
+            .filter(|(hit, _)| *hit < len)
+            .collect();
+        let mut seconds = BTreeMap::new();
+        for (hit, blow) in feedbacks {
+            let history = History { pins: pins.clone(), hints: hints(hit, blow, len) };
+            let guess = solver.next_guess(&[ history ])?;
+            seconds.insert((hit, blow), space.rank(Code::from_pins(&guess, &palette)?)?);
+        }
+        Some(Self { first: space.rank(first)?, seconds })
+    }
+
+    fn lines(&self, key: &str, name: &str) -> Vec<String> {
+        let mut lines = vec![ format!("{} {} - {}", key, name, self.first) ];
+        lines.extend(self.seconds.iter().map(|((hit, blow), guess)| format!("{} {} {}-{} {}", key, name, hit, blow, guess)));
+        lines
+    }
+}
+
+// すべてのプリセットのルールと戦略の定跡を出力する
+pub fn run(args: &Args) -> crate::Result<()> {
+    println!("# ルール 戦略 最初の回答のヒント(Hit数-Blow数、最初の回答は -) 回答(CodeSpace の番号)");
+    for name in crate::RULES {
+        let rule = Rule::preset(name).ok_or(Error::Args { arg: name.to_string() })?;
+        for strategy in Strategy::ALL {
+            let book = Book::generate(&rule, strategy, args.threads, FeedbackTable::load_or_new(&rule)).ok_or(Error::NoCandidate)?;
+            for line in book.lines(&rule.code_space().key(), strategy.name()) {
+                println!("{}", line);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn book_default() {
+        // 埋め込んだ定跡がソルバーの回答と同じ(表はキャッシュに書かずに作る)
+        let rule = Rule::default();
+        for strategy in Strategy::ALL {
+            let book = Book::find(&rule, strategy).unwrap();
+            let generated = Book::generate(&rule, strategy, None, FeedbackTable::new(&rule)).unwrap();
+            assert_eq!(book.first, generated.first);
+            assert_eq!(book.seconds, generated.seconds);
+        }
+
+        let book = Book::parse("# comment\n6c4pu minimax - 3\n6c4pu minimax 0-2 5\n6c4pd minimax - 7\n", "6c4pu", "minimax").unwrap();
+        assert_eq!(book.first(), 3);
+        assert_eq!(book.second(0, 2), Some(5));
+        assert_eq!(book.second(1, 2), None);
+        assert!(Book::parse("6c4pu minimax 0-2 5\n", "6c4pu", "minimax").is_none());
+    }
+}
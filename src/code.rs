@@ -0,0 +1,192 @@
+use crate::{Pin, Rule};
+
+// 回答を色の番号(1から)で 4bit ずつ詰めたもの。最初のピンが上位になるので、大小は並びの順と同じ
+// 色の番号はルールのピンを並べたもの(palette)の位置
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Code(u64);
+
+impl Code {
+
+    pub const MAX_COLORS: usize = 15;
+    pub const MAX_LEN: usize = 16;
+
+    // 下位から 4bit ごとに 1 を立てたもの
+    const LOW_BITS: u64 = 0x1111_1111_1111_1111;
+
+    pub fn from_pins(pins: &[Pin], palette: &[Pin]) -> Option<Code> {
+        if pins.len() > Self::MAX_LEN || palette.len() > Self::MAX_COLORS { return None; }
+        pins.iter().try_fold(0, |code, pin| {
+            let color = palette.iter().position(|p| p == pin)? as u64 + 1;
+            Some(code << 4 | color)
+        }).map(Code)
+    }
+
+    pub fn to_pins(self, palette: &[Pin]) -> Vec<Pin> {
+        (0..self.len()).rev().map(|i| palette[self.color(i) - 1]).collect()
+    }
+
+    pub fn len(self) -> usize {
+        (64 - self.0.leading_zeros() as usize).div_ceil(4)
+    }
+
+    // 下から i 番目の色の番号
+    fn color(self, i: usize) -> usize {
+        (self.0 >> (i * 4) & 0xF) as usize
+    }
+
+    // answer に対してこの回答をしたときの (Hit 数, Blow 数)
+    pub fn hit_blow(self, answer: Code) -> (usize, usize) {
+        let len = self.len();
+        // 色が違う 4bit を数える
+        let diff = self.0 ^ answer.0;
+        let diff = diff | diff >> 1;
+        let diff = (diff | diff >> 2) & Self::LOW_BITS;
+        let hit = len - diff.count_ones() as usize;
+
+        let mut counts = [ [0u8; Self::MAX_COLORS + 1]; 2 ];
+        for i in 0..len {
+            counts[0][self.color(i)] += 1;
+            counts[1][answer.color(i)] += 1;
+        }
+        let common: usize = (1..=Self::MAX_COLORS).map(|c| counts[0][c].min(counts[1][c]) as usize).sum();
+        (hit, common - hit)
+    }
+
+    // ヒントの番号 (Hit 数, Blow 数 から 0..(len+1)^2)
+    pub fn feedback(self, answer: Code) -> usize {
+        let (hit, blow) = self.hit_blow(answer);
+        hit * (self.len() + 1) + blow
+    }
+}
+
+// ルールで作れるすべての回答。並びの順に 0 から番号をつける
+pub struct CodeSpace {
+    colors: usize,
+    len: usize,
+    duplicate: bool,
+}
+
+impl CodeSpace {
+
+    pub fn new(rule: &Rule) -> Self {
+        Self { colors: rule.pins.len(), len: rule.answer_count as usize, duplicate: rule.duplicate }
+    }
+
+    // 回答の数
+    pub fn count(&self) -> usize {
+        (0..self.len).map(|pos| self.choices(pos)).product()
+    }
+
+    // pos 番目のピンに選べる色の数
+    fn choices(&self, pos: usize) -> usize {
+        if self.duplicate { self.colors } else { self.colors.saturating_sub(pos) }
+    }
+
+    // pos 番目のピンまでが決まったときに、残りのピンで作れる並びの数
+    fn block(&self, pos: usize) -> usize {
+        (pos + 1..self.len).map(|p| self.choices(p)).product()
+    }
+
+    pub fn rank(&self, code: Code) -> Option<usize> {
+        if code.len() != self.len { return None; }
+        let mut used = 0u32;
+        let mut index = 0;
+        for pos in 0..self.len {
+            let color = code.color(self.len - 1 - pos) - 1;
+            if color >= self.colors { return None; }
+            // 重複なしなら、まだ使っていない色の中で何番目か
+            let order = if self.duplicate {
+                color
+            } else {
+                if used & 1 << color != 0 { return None; }
+                color - (used & ((1 << color) - 1)).count_ones() as usize
+            };
+            used |= 1 << color;
+            index += order * self.block(pos);
+        }
+        Some(index)
+    }
+
+    pub fn unrank(&self, index: usize) -> Option<Code> {
+        if index >= self.count() { return None; }
+        let mut used = 0u32;
+        let mut rest = index;
+        let mut code = 0;
+        for pos in 0..self.len {
+            let block = self.block(pos);
+            let order = rest / block;
+            rest %= block;
+            let color = if self.duplicate {
+                order
+            } else {
+                (0..self.colors).filter(|c| used & 1 << c == 0).nth(order)?
+            };
+            used |= 1 << color;
+            code = code << 4 | (color as u64 + 1);
+        }
+        Some(Code(code))
+    }
+
+    // 同じ数え方になるルールを区別する名前 (例: 6 色 4 個 重複あり は "6c4pd")
+    // 色そのものは含めないので、色の違うルールでも同じ名前になる
+    pub fn key(&self) -> String {
+        format!("{}c{}p{}", self.colors, self.len, if self.duplicate { "d" } else { "u" })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Code> + '_ {
+        (0..self.count()).filter_map(|index| self.unrank(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rule, hit_blow};
+
+    #[test]
+    fn code_hit_blow() {
+        let rule = Rule::preset("classic").unwrap();
+        let palette = rule.palette();
+        let codes = rule.codes();
+        let packed: Vec<Code> = codes.iter().map(|pins| Code::from_pins(pins, &palette).unwrap()).collect();
+
+        // 並びの順と大小が同じ
+        assert!(packed.windows(2).all(|w| w[0] < w[1]));
+        for (pins, code) in codes.iter().zip(&packed) {
+            assert_eq!(code.len(), 4);
+            assert_eq!(&code.to_pins(&palette), pins);
+        }
+        // ゲームの判定と同じ結果
+        for (a, code_a) in codes.iter().zip(&packed).step_by(7) {
+            for (b, code_b) in codes.iter().zip(&packed) {
+                assert_eq!(code_b.hit_blow(*code_a), hit_blow(a, b));
+            }
+        }
+
+        assert!(Code::from_pins(&[ Pin::purple() ], &palette).is_none());
+    }
+
+    #[test]
+    fn code_space() {
+        for (name, count) in [ ("default", 360), ("classic", 1296), ("super", 32768) ] {
+            let rule = Rule::preset(name).unwrap();
+            let space = CodeSpace::new(&rule);
+            assert_eq!(space.count(), count);
+
+            let codes: Vec<Code> = space.iter().collect();
+            assert_eq!(codes.len(), count);
+            assert!(codes.windows(2).all(|w| w[0] < w[1]));
+            for (index, code) in codes.iter().enumerate() {
+                assert_eq!(space.rank(*code), Some(index));
+            }
+            assert!(space.unrank(count).is_none());
+        }
+
+        // 重複なしのルールでは、同じ色を含む回答に番号はない
+        let rule = Rule::default();
+        let palette = rule.palette();
+        let space = CodeSpace::new(&rule);
+        assert_eq!(space.rank(Code::from_pins(&[ Pin::red(), Pin::red(), Pin::blue(), Pin::green() ], &palette).unwrap()), None);
+        assert_eq!(space.rank(Code::from_pins(&[ Pin::red(), Pin::blue() ], &palette).unwrap()), None);
+    }
+}
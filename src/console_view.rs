@@ -1,11 +1,16 @@
 use std::fmt;
 use std::io::Write;
+use std::mem;
 
 use crossterm::{cursor, event, execute, queue, style, terminal };
 use crossterm::event::{Event, KeyCode};
 use crossterm::style::Stylize;
 
-use crate::{Pin, Hint, History};
+use crate::{Pin, Hint, History, Summary};
+use crate::analysis::Analysis;
+use crate::leaderboard::{Entry, Leaderboard};
+use crate::record::{self, Record};
+use crate::stats::Stats;
 
 impl fmt::Display for Pin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -25,11 +30,63 @@ impl fmt::Display for Hint {
 
 struct Position { x: u16, y: u16 }
 
+// 入力待ちの結果
+pub enum Input {
+    Answer(Vec<Pin>),   // 回答
+    Hint,               // 次の回答のヒントがほしい
+    Escape(Vec<Option<Pin>>),   // やめたい。入力途中の回答を持って帰る
+    TakeBack,           // 最後の回答を取り消したい(練習のときだけ)
+}
+
+// 回答を決めるときの確認
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Confirm {
+    Always,         // いつも確認する
+    Off,            // 確認しない
+    Contradiction,  // これまでのヒントと矛盾する回答のときだけ確認する
+    Quick,          // すべての位置にピンを置いたら、すぐに決定する
+}
+
+impl Confirm {
+
+    pub const ALL: [Confirm; 4] = [ Confirm::Always, Confirm::Off, Confirm::Contradiction, Confirm::Quick ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Confirm::Always => "always",
+            Confirm::Off => "off",
+            Confirm::Contradiction => "contradiction",
+            Confirm::Quick => "quick",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|confirm| confirm.name() == name)
+    }
+
+    // 回答 pins を決める前に確認するか
+    fn asks(&self, histories: &[History], pins: &[Pin]) -> bool {
+        match self {
+            Confirm::Always => true,
+            Confirm::Contradiction => !histories.iter().all(|history| history.is_consistent(pins)),
+            Confirm::Off | Confirm::Quick => false,
+        }
+    }
+}
+
+// ゲーム終了後にすること
+#[derive(PartialEq, Debug)]
+pub enum EndAction {
+    Quit,
+    Analysis,   // 回答の分析を見る
+    Stats,      // 成績を見る
+}
+
 struct AnswerView {
     answer: Vec<Option<Pin>>,
 }
 
-impl<'a> fmt::Display for AnswerView {
+impl fmt::Display for AnswerView {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "|")?;
         self.answer.iter()
@@ -47,32 +104,48 @@ pub struct ConsoleView {
     height: u16,
     answer_count: u32,
     try_count: u32,
+    duplicate: bool,
+    practice: bool,     // 最後の回答を取り消せる
+    hints: bool,        // ヒントを使える
+    confirm: Confirm,
     pinnum_group: SelectGroup<u32>,
     pins_group: SelectGroup<Pin>,
 }
 
 impl ConsoleView {
 
-    pub fn new(pins: &[&Pin], answer_count: u32, try_count: u32) -> Self {
+    pub fn new(pins: &[&Pin], answer_count: u32, try_count: u32, duplicate: bool) -> Self {
         let (width, height) = terminal::size().unwrap();
 
         let keys = [ '1', '2', '3', '4', '5', '6', '7', '8', '9', '0' ];
         let pinnum_group = SelectGroup::new(
             Position { x: (width / 2) - (answer_count as u16 * 5 / 2), y: 2 + try_count as u16 + 2 },
             keys.into_iter().zip(1..=answer_count)
-            .map(|(key, item)| KeyItem { key, item: item.clone() })
+            .map(|(key, item)| KeyItem { key, item })
             .collect());
 
         let keys = [ 'q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p' ];
         let pins_group = SelectGroup::new(
-            Position { x: (width / 2) - (((pins.len() * 4) + pins.len() - 1) / 2) as u16, y: pinnum_group.position.y + 2 },
+            Position { x: (width / 2) - ((pins.len() * 4) + pins.len() - 1).div_ceil(2) as u16, y: pinnum_group.position.y + 2 },
             keys.into_iter().zip(pins.iter())//.map(|p| *p))
-                        .map(|(key, item)| KeyItem { key, item: (*item).clone() } )
+                        .map(|(key, item)| KeyItem { key, item: **item } )
                         .collect());
 
         execute!(std::io::stdout(), terminal::EnterAlternateScreen).unwrap();
 
-        Self { width, height, try_count, answer_count, pinnum_group, pins_group }
+        Self { width, height, try_count, answer_count, duplicate, practice: false, hints: true, confirm: Confirm::Always, pinnum_group, pins_group }
+    }
+
+    pub fn set_practice(&mut self, practice: bool) {
+        self.practice = practice;
+    }
+
+    pub fn set_hints(&mut self, hints: bool) {
+        self.hints = hints;
+    }
+
+    pub fn set_confirm(&mut self, confirm: Confirm) {
+        self.confirm = confirm;
     }
 
     pub fn update(&self) -> crate::Result<()> {
@@ -82,7 +155,7 @@ impl ConsoleView {
             terminal::Clear(terminal::ClearType::All),
             cursor::MoveTo((self.width / 2) - 10, 1), style::Print("マスター　マインド".yellow()),
         )?;
-        let x = (self.width / 2) - (((5 * self.answer_count) + 1) / 2) as u16 - 1;
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
         for i in 1..=self.try_count {
             queue!(stdout,
                 cursor::MoveTo(x - 4, 2 + i as u16),
@@ -92,26 +165,35 @@ impl ConsoleView {
         queue!(stdout,
             cursor::MoveTo(self.pinnum_group.position.x - 34, self.pinnum_group.position.y), style::Print("ピンの位置を選択してください"),
             cursor::MoveTo(self.pins_group.position.x - 30, self.pins_group.position.y), style::Print("ピンを選択してください"),
+            cursor::MoveTo(self.pins_group.position.x - 30, self.pins_group.position.y + 2),
+            style::Print(format!("元に戻す: Z  やり直す: X{}", if self.practice { "  回答を取り消す: B" } else { "" })),
             cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC  "),
-            cursor::MoveTo(0, self.height - 1),
         )?;
+        if self.hints {
+            queue!(stdout, cursor::MoveTo(self.width - 10, self.height - 3), style::Print("ヒント: H"))?;
+        }
+        queue!(stdout, cursor::MoveTo(0, self.height - 1))?;
         stdout.flush()?;
         
         Ok(())
     }
 
-    pub fn wait_input(&mut self, histories: &[History]) -> crate::Result<Vec<Pin>> {
+    // prefill があれば、その回答(ヒントや入力途中の回答)を入力済みにして始める
+    // remainings があれば、各回答の右に残っている答えの数を表示する
+    pub fn wait_input(&mut self, histories: &[History], remainings: &[usize], prefill: Option<&[Option<Pin>]>) -> crate::Result<Input> {
 
-        let x = (self.width / 2) - (((5 * self.answer_count) + 1) / 2) as u16 - 1;
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
         let y = 2 + self.try_count as u16;
-        for (i, history) in histories.iter().enumerate() {
-            view_history(x, y - i as u16, history);
+        self.view_histories(histories, remainings);
+
+        let mut answer = AnswerWindow::new(Position { x, y: y - histories.len() as u16 }, self.answer_count, self.duplicate);
+        if let Some(pins) = prefill.filter(|pins| pins.len() == self.answer_count as usize) {
+            answer.answer.answer = pins.to_vec();
+            answer.update();
+            execute!(std::io::stdout(),
+                cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
+                style::Print("回答を入力しておきました"))?;
         }
-
-        let mut answer = AnswerWindow {
-            position: Position { x, y: y - histories.len() as u16 },
-            answer: AnswerView { answer: vec![None; self.answer_count as usize] },
-        };
         
         self.pinnum_group.update_line();
         self.pins_group.update_line();
@@ -133,16 +215,29 @@ impl ConsoleView {
             match event {
                 Event::Key(key) if key.kind == event::KeyEventKind::Release => {
                     match key.code {
-                        KeyCode::Esc => return Err(Box::new(crate::Error::EndOfEscape)),
+                        KeyCode::Esc => return Ok(Input::Escape(answer.answer.answer.clone())),
+                        // ヒントを使えなければ何もしない(入力中の回答はそのまま)
+                        KeyCode::Char('h') if self.hints => return Ok(Input::Hint),
+                        KeyCode::Char('h') => (),
+                        KeyCode::Char('b') if self.practice && !histories.is_empty() => return Ok(Input::TakeBack),
+                        KeyCode::Char('z') => {
+                            let message = if answer.undo() { "元に戻しました" } else { "戻せる入力はありません" };
+                            answer.update();
+                            execute!(std::io::stdout(),
+                                cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine), style::Print(message))?;
+                        },
+                        KeyCode::Char('x') => {
+                            let message = if answer.redo() { "やり直しました" } else { "やり直せる入力はありません" };
+                            answer.update();
+                            execute!(std::io::stdout(),
+                                cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine), style::Print(message))?;
+                        },
                         KeyCode::Char(ch) => {
                             if let Some(num) = self.pinnum_group.select(Some(ch)) {
                                 if let Some(pin) = self.pins_group.select_value() {
-                                    answer.input_pin((num - 1) as usize, pin);
-                                    self.pinnum_group.select(None);
-                                    self.pins_group.select(None);
-                                    execute!(std::io::stdout(),
-                                        cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
-                                        style::Print(format!("ピン: {} を 位置: {} にセット", pin, num)))?;
+                                    if let Some(input) = self.place_pin(&mut answer, num, pin)? {
+                                        return Ok(input);
+                                    }
                                 } else {
                                     execute!(std::io::stdout(),
                                         cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
@@ -150,12 +245,9 @@ impl ConsoleView {
                                 }
                             } else if let Some(pin) = self.pins_group.select(Some(ch)) {
                                 if let Some(num) = self.pinnum_group.select_value() {
-                                    answer.input_pin((num - 1) as usize, pin);
-                                    self.pinnum_group.select(None);
-                                    self.pins_group.select(None);
-                                    execute!(std::io::stdout(),
-                                        cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
-                                        style::Print(format!("ピン: {} を 位置: {} にセット", pin, num)))?;
+                                    if let Some(input) = self.place_pin(&mut answer, num, pin)? {
+                                        return Ok(input);
+                                    }
                                 } else {
                                     execute!(std::io::stdout(),
                                         cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
@@ -166,26 +258,31 @@ impl ConsoleView {
                                     cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine), style::Print(format!("'{}' キー じゃないよ", ch)))?;
                             }
                         },
-                        KeyCode::Enter => {
-                            if answer.answer.answer.iter().all(|a| a.is_some()) {
-                                execute!(std::io::stdout(),
-                                    cursor::MoveTo(answer.position.x + (answer.answer.answer.len() * 5 + 1) as u16 + 2 + 9 + 2, answer.position.y),
-                                    style::Print("本当にいいですか？ (y/n)"))?;
-                                loop {
-                                    let event = event::read()?;
-                                    match event {
-                                        Event::Key(key) if key.kind == event::KeyEventKind::Release => {
-                                            match key.code {
-                                                KeyCode::Char(ch) if ch == 'y' => {
-                                                    return Ok(answer.answer.answer.iter().map(|a| a.unwrap()).collect());
-                                                },
-                                                KeyCode::Char(ch) if ch == 'n' => break,
-                                                KeyCode::Esc => return Err(Box::new(crate::Error::EndOfEscape)),
-                                                _ => (),
-                                            }
-                                        },
-                                        _ => (),
-                                    }
+                        KeyCode::Enter if answer.pins().is_some() => {
+                            let pins = answer.pins().unwrap();
+                            if !self.confirm.asks(histories, &pins) {
+                                return Ok(Input::Answer(pins));
+                            }
+                            let message = if self.confirm == Confirm::Contradiction {
+                                "ヒントと矛盾しています。本当にいいですか？ (y/n)"
+                            } else {
+                                "本当にいいですか？ (y/n)"
+                            };
+                            execute!(std::io::stdout(),
+                                cursor::MoveTo(answer.position.x + (answer.answer.answer.len() * 5 + 1) as u16 + 2 + 9 + 2, answer.position.y),
+                                style::Print(message))?;
+                            loop {
+                                let event = event::read()?;
+                                match event {
+                                    Event::Key(key) if key.kind == event::KeyEventKind::Release => {
+                                        match key.code {
+                                            KeyCode::Char('y') => return Ok(Input::Answer(pins)),
+                                            KeyCode::Char('n') => break,
+                                            KeyCode::Esc => return Ok(Input::Escape(answer.answer.answer.clone())),
+                                            _ => (),
+                                        }
+                                    },
+                                    _ => (),
                                 }
                             }
                         },
@@ -195,58 +292,429 @@ impl ConsoleView {
                 _ => (),
             }
         }
+    }
 
-        Ok(Vec::new())
+    // 位置 num (1 から) にピンを置いて選択を解く
+    // すぐに決定する設定で、すべての位置にピンがあれば、その回答を返す
+    fn place_pin(&mut self, answer: &mut AnswerWindow, num: u32, pin: Pin) -> crate::Result<Option<Input>> {
+        answer.input_pin((num - 1) as usize, pin);
+        self.pinnum_group.select(None);
+        self.pins_group.select(None);
+        if let (Confirm::Quick, Some(pins)) = (self.confirm, answer.pins()) {
+            return Ok(Some(Input::Answer(pins)));
+        }
+        execute!(std::io::stdout(),
+            cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
+            style::Print(format!("ピン: {} を 位置: {} にセット", pin, num)))?;
+        Ok(None)
     }
 
-    pub fn win(&self, histories: &[History]) {
-        let x = (self.width / 2) - (((5 * self.answer_count) + 1) / 2) as u16 - 1;
-        let y = 2 + self.try_count as u16;
-        for (i, history) in histories.iter().enumerate() {
-            view_history(x, y - i as u16, history);
+    // やめるときに保存するか。保存するなら Some(true)、しないなら Some(false)、やめないなら None
+    pub fn ask_save(&self) -> crate::Result<Option<bool>> {
+        execute!(std::io::stdout(),
+            cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
+            style::Print("保存して終了: Y  保存しないで終了: N  続ける: ESC"))?;
+        loop {
+            let event = event::read()?;
+            match event {
+                Event::Key(key) if key.kind == event::KeyEventKind::Release => {
+                    let answer = match key.code {
+                        KeyCode::Char('y') => Some(true),
+                        KeyCode::Char('n') => Some(false),
+                        KeyCode::Esc => None,
+                        _ => continue,
+                    };
+                    execute!(std::io::stdout(), cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine))?;
+                    return Ok(answer);
+                },
+                _ => (),
+            }
         }
+    }
+
+    // 名前を聞いてランキングに登録し、ランキングを見せてから結果を表示する
+    // 点数がない(ランク外の)ときや、ランキングを読めなかったときは結果だけ
+    pub fn win(&self, histories: &[History], summary: &Summary, board: Option<&mut Leaderboard>) -> EndAction {
+        let Some(score) = summary.score else {
+            return self.view_result(histories, summary, "ゲーム  クリア！  (回答を取り消したのでランク外)");
+        };
+        let Some(board) = board else {
+            return self.view_result(histories, summary, "ゲーム  クリア！  (ランキングを読めないので登録しません)");
+        };
+        let rank = self.input_name(score)
+            .and_then(|name| board.insert(Entry::new(summary.rule, &name, score, histories.len(), summary.elapsed, summary.hint_count)));
+        self.view_leaderboard(summary.rule, &board.top(summary.rule), rank);
+        self.update().unwrap();
+        self.view_result(histories, summary, "ゲーム  クリア！  おめでとう！！")
+    }
 
+    // ランキングに登録する名前。登録しないなら None
+    fn input_name(&self, score: u32) -> Option<String> {
+        let y = 2 + self.try_count as u16;
+        let message = "ゲーム  クリア！  おめでとう！！";
         execute!(std::io::stdout(),
             cursor::MoveTo(1, y + 1), terminal::Clear(terminal::ClearType::FromCursorDown),
-            cursor::MoveTo(self.width / 2 - 16, y + 2), style::Print("ゲーム  クリア！  おめでとう！！"),
-            cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC"),
+            cursor::MoveTo(self.width / 2 - message.chars().count() as u16, y + 2), style::Print(message),
+            cursor::MoveTo(self.width / 2 - 10, y + 4), style::Print(format!("スコア: {}", score)),
+            cursor::MoveTo(self.width - 16, self.height - 3), style::Print("登録: ENT"),
+            cursor::MoveTo(self.width - 16, self.height - 2), style::Print("登録しない: ESC"),
         ).unwrap();
 
+        let mut name = String::new();
         loop {
+            execute!(std::io::stdout(),
+                cursor::MoveTo(self.width / 2 - 10, y + 6), terminal::Clear(terminal::ClearType::UntilNewLine),
+                style::Print(format!("名前: {}_", name))).unwrap();
             let event = event::read().unwrap();
             match event {
-                Event::Key(key) if key.kind == event::KeyEventKind::Release && key.code == KeyCode::Esc
-                    => return,
+                Event::Key(key) if key.kind == event::KeyEventKind::Release => {
+                    match key.code {
+                        // 区切りの ',' はランキングのファイルで使うので入れられない
+                        KeyCode::Char(ch) if !ch.is_control() && ch != ',' && name.chars().count() < Leaderboard::NAME_MAX => name.push(ch),
+                        KeyCode::Backspace => { name.pop(); },
+                        KeyCode::Enter if !name.trim().is_empty() => return Some(name.trim().to_string()),
+                        KeyCode::Esc => return None,
+                        _ => (),
+                    }
+                },
                 _ => (),
             }
         }
     }
 
-    pub fn game_over(&self, histories: &[History]) {
-        let x = (self.width / 2) - (((5 * self.answer_count) + 1) / 2) as u16 - 1;
+    // ルールのランキング。rank があればその行を目立たせる
+    fn view_leaderboard(&self, rule: &str, entries: &[&Entry], rank: Option<usize>) {
+        let x = (self.width / 2).saturating_sub(24);
+        let mut stdout = std::io::stdout();
+        queue!(stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo((self.width / 2) - 8, 1), style::Print(format!("ランキング ({})", rule).yellow()),
+        ).unwrap();
+        for (i, entry) in entries.iter().enumerate() {
+            let elapsed = entry.elapsed.as_secs();
+            let line = format!("{:>2}. {:<16} {:>6}点  {:>2}回  {:02}:{:02}  ヒント {}回",
+                i + 1, entry.name, entry.score, entry.guesses, elapsed / 60, elapsed % 60, entry.hint_count);
+            queue!(stdout, cursor::MoveTo(x, 3 + i as u16)).unwrap();
+            if rank == Some(i) {
+                queue!(stdout, style::Print(line.yellow())).unwrap();
+            } else {
+                queue!(stdout, style::Print(line)).unwrap();
+            }
+        }
+        if entries.is_empty() {
+            queue!(stdout, cursor::MoveTo(x, 3), style::Print("まだ登録がありません")).unwrap();
+        }
+        queue!(stdout, cursor::MoveTo(self.width - 10, self.height - 2), style::Print("次へ: ESC")).unwrap();
+        stdout.flush().unwrap();
+
+        wait_escape();
+    }
+
+    pub fn game_over(&self, histories: &[History], summary: &Summary) -> EndAction {
+        self.view_result(histories, summary, "ゲーム  オーバー  残念")
+    }
+
+    fn view_histories(&self, histories: &[History], remainings: &[usize]) {
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
         let y = 2 + self.try_count as u16;
         for (i, history) in histories.iter().enumerate() {
             view_history(x, y - i as u16, history);
         }
+        // 各回答の右に、その時点で残っていた答えの数
+        let hints_x = x + (self.answer_count * 5 + 1) as u16 + 1 + self.answer_count as u16 * 2 + 2;
+        for (i, remaining) in remainings.iter().enumerate() {
+            execute!(std::io::stdout(),
+                cursor::MoveTo(hints_x, y - i as u16), style::Print(format!("残り {}", remaining))).unwrap();
+        }
+    }
+
+    fn view_result(&self, histories: &[History], summary: &Summary, message: &str) -> EndAction {
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
+        let y = 2 + self.try_count as u16;
+        self.view_histories(histories, &summary.remainings);
 
+        let elapsed = summary.elapsed.as_secs();
         execute!(std::io::stdout(),
             cursor::MoveTo(1, y + 1), terminal::Clear(terminal::ClearType::FromCursorDown),
-            cursor::MoveTo(self.width / 2 - 11, y + 2), style::Print("ゲーム  オーバー  残念"),
+            cursor::MoveTo(self.width / 2 - message.chars().count() as u16, y + 2), style::Print(message),
+            cursor::MoveTo(x - 4, y + 4), style::Print(format!("答え {}", HistoryPins(summary.answer))),
+            cursor::MoveTo(x - 4, y + 6),
+            style::Print(format!("回数: {}/{}  ヒント: {}回  時間: {:02}:{:02}  シード: {}",
+                histories.len(), self.try_count, summary.hint_count, elapsed / 60, elapsed % 60, summary.seed)),
+            cursor::MoveTo(x - 4, y + 7), terminal::Clear(terminal::ClearType::UntilNewLine),
+            style::Print(summary.score.map(|score| format!("スコア: {}", score)).unwrap_or_default()),
+            cursor::MoveTo(self.width - 10, self.height - 4), style::Print("分析: A"),
+            cursor::MoveTo(self.width - 10, self.height - 3), style::Print("成績: S"),
             cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC"),
         ).unwrap();
 
         loop {
             let event = event::read().unwrap();
             match event {
-                Event::Key(key) if key.kind == event::KeyEventKind::Release && key.code == KeyCode::Esc
-                    => return,
+                Event::Key(key) if key.kind == event::KeyEventKind::Release => {
+                    match key.code {
+                        KeyCode::Esc => return EndAction::Quit,
+                        KeyCode::Char('a') => return EndAction::Analysis,
+                        KeyCode::Char('s') => return EndAction::Stats,
+                        _ => (),
+                    }
+                },
                 _ => (),
             }
         }
     }
+
+    // 回答ごとの分析
+    pub fn view_analysis(&self, histories: &[History], analyses: &[Analysis]) {
+        let mut stdout = std::io::stdout();
+        queue!(stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo((self.width / 2) - 4, 1), style::Print("分析".yellow()),
+        ).unwrap();
+        for (i, (history, analysis)) in histories.iter().zip(analyses).enumerate() {
+            let y = 3 + i as u16 * 2;
+            // ソルバーの回答と同じくらい情報が得られる回答なら ◎
+            let mark = if analysis.guess.entropy + 0.005 >= analysis.best_partition.entropy { "◎" } else { "  " };
+            queue!(stdout,
+                cursor::MoveTo(2, y),
+                style::Print(format!("{:>2}: {} {} {}  候補 {} → {}  得た情報 {:.2}bit",
+                    i + 1, HistoryPins(&history.pins), HistoryHints(&history.hints), mark,
+                    analysis.before, analysis.after, analysis.bits())),
+                cursor::MoveTo(6, y + 1),
+                style::Print(format!("あなた: 期待 {:.2}bit 最大 {} ({}通り)   最善 {}: 期待 {:.2}bit 最大 {} ({}通り)",
+                    analysis.guess.entropy, analysis.guess.worst, analysis.guess.parts,
+                    HistoryPins(&analysis.best), analysis.best_partition.entropy, analysis.best_partition.worst, analysis.best_partition.parts)),
+            ).unwrap();
+        }
+        queue!(stdout, cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC")).unwrap();
+        stdout.flush().unwrap();
+
+        wait_escape();
+    }
+
+    // ルールごとの成績。ルールを横に並べる
+    pub fn view_stats(&self, stats: &[(&str, Stats)]) {
+        // 棒グラフの最大の長さ
+        const BAR: usize = 16;
+        let mut stdout = std::io::stdout();
+        queue!(stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo((self.width / 2) - 2, 1), style::Print("成績".yellow()),
+        ).unwrap();
+        for (i, (name, stats)) in stats.iter().enumerate() {
+            let x = 2 + i as u16 * 32;
+            queue!(stdout,
+                cursor::MoveTo(x, 3), style::Print(name.yellow()),
+                cursor::MoveTo(x, 4), style::Print(format!("ゲーム: {}  勝率: {:.0}%", stats.games, stats.win_rate())),
+                cursor::MoveTo(x, 5), style::Print(format!("連勝: {}  最高: {}", stats.streak, stats.best_streak)),
+            ).unwrap();
+            let max = stats.histogram.iter().max().copied().unwrap_or(0).max(1);
+            for (j, count) in stats.histogram.iter().enumerate() {
+                queue!(stdout,
+                    cursor::MoveTo(x, 7 + j as u16),
+                    style::Print(format!("{:>2}: {:>4} {}", j + 1, count, "#".repeat((count * BAR).div_ceil(max)))),
+                ).unwrap();
+            }
+        }
+        queue!(stdout, cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC")).unwrap();
+        stdout.flush().unwrap();
+
+        wait_escape();
+    }
+
+    // コンピュータが当てるモードの画面
+    pub fn update_breaker(&self) -> crate::Result<()> {
+        let mut stdout = std::io::stdout();
+        queue!(stdout,
+            cursor::Hide,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo((self.width / 2) - 10, 1), style::Print("マスター　マインド".yellow()),
+        )?;
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
+        for i in 1..=self.try_count {
+            queue!(stdout,
+                cursor::MoveTo(x - 4, 2 + i as u16),
+                style::Print(format!("{:>2}: {}", i, AnswerView { answer: vec![None; self.answer_count as usize] })),
+            )?;
+        }
+        let message = "答えを考えてください。コンピュータの回答への Hit と Blow の数を数字キーで入力してください";
+        queue!(stdout,
+            cursor::MoveTo((self.width / 2).saturating_sub(message.chars().count() as u16), self.try_count as u16 + 4), style::Print(message),
+            cursor::MoveTo(self.width - 10, self.height - 3), style::Print("訂正: BS"),
+            cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC  "),
+            cursor::MoveTo(0, self.height - 1),
+        )?;
+        stdout.flush()?;
+
+        Ok(())
+    }
+
+    // コンピュータの回答 pins に対する Hit と Blow の数を入力してもらう
+    pub fn wait_feedback(&mut self, histories: &[History], pins: &[Pin]) -> crate::Result<(usize, usize)> {
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
+        let y = 2 + self.try_count as u16 - histories.len() as u16;
+        self.view_histories(histories, &[]);
+        execute!(std::io::stdout(), cursor::MoveTo(x, y), style::Print(HistoryPins(pins)))?;
+
+        // Hit, Blow の順に入力された数
+        let mut counts: Vec<usize> = Vec::new();
+        loop {
+            {
+                let show = |count: Option<&usize>| count.map(|c| c.to_string()).unwrap_or(String::from("_"));
+                let mut stdout = std::io::stdout();
+                queue!(stdout,
+                    cursor::MoveTo(x + (self.answer_count * 5 + 1) as u16 + 2, y),
+                    terminal::Clear(terminal::ClearType::UntilNewLine),
+                    style::Print(format!("Hit: {}  Blow: {}", show(counts.first()), show(counts.get(1)))))?;
+                if counts.len() == 2 {
+                    queue!(stdout, style::Print("  決定: ENT"))?;
+                }
+                stdout.flush()?;
+            }
+
+            let event = event::read()?;
+            match event {
+                Event::Key(key) if key.kind == event::KeyEventKind::Release => {
+                    match key.code {
+                        KeyCode::Esc => return Err(Box::new(crate::Error::EndOfEscape)),
+                        KeyCode::Char(ch) if ch.is_ascii_digit() && counts.len() < 2 => {
+                            counts.push(ch.to_digit(10).unwrap() as usize);
+                        },
+                        KeyCode::Backspace => { counts.pop(); },
+                        KeyCode::Enter if counts.len() == 2 => {
+                            if counts[0] + counts[1] <= self.answer_count as usize {
+                                return Ok((counts[0], counts[1]));
+                            }
+                            execute!(std::io::stdout(),
+                                cursor::MoveTo(4, self.height - 4), terminal::Clear(terminal::ClearType::CurrentLine),
+                                style::Print("Hit と Blow の合計がピンの数より多いです"))?;
+                        },
+                        _ => (),
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+
+    // ソルバーが考えている間の進み具合を表示する
+    pub fn progress(&self) -> Box<dyn FnMut(usize, usize)> {
+        let y = self.height - 4;
+        // 終わったら行を消す
+        Box::new(move |done, total| {
+            let message = if done >= total { String::new() } else { format!("考え中... {:>3}%", done * 100 / total.max(1)) };
+            execute!(std::io::stdout(),
+                cursor::MoveTo(4, y), terminal::Clear(terminal::ClearType::CurrentLine), style::Print(message)).unwrap();
+        })
+    }
+
+    // 候補がこの数以下なら一覧を表示する
+    const CANDIDATES_MAX: usize = 8;
+
+    // 残っている候補の数と、少なければその一覧、おすすめの回答
+    pub fn view_candidates(&self, candidates: &[Vec<Pin>], recommended: Option<&[Pin]>) {
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
+        let x = x + (self.answer_count * 5 + 1) as u16 + 1 + self.answer_count as u16 * 4 + 12;
+        let mut stdout = std::io::stdout();
+        queue!(stdout,
+            cursor::MoveTo(x, 3), terminal::Clear(terminal::ClearType::UntilNewLine),
+            style::Print(format!("候補: {}", candidates.len())),
+            cursor::MoveTo(x, 4), terminal::Clear(terminal::ClearType::UntilNewLine)).unwrap();
+        if let Some(pins) = recommended {
+            queue!(stdout, style::Print(format!("おすすめ: {}", HistoryPins(pins)))).unwrap();
+        }
+        for i in 0..Self::CANDIDATES_MAX {
+            queue!(stdout, cursor::MoveTo(x, 6 + i as u16), terminal::Clear(terminal::ClearType::UntilNewLine)).unwrap();
+            if candidates.len() <= Self::CANDIDATES_MAX {
+                if let Some(pins) = candidates.get(i) {
+                    queue!(stdout, style::Print(HistoryPins(pins))).unwrap();
+                }
+            }
+        }
+        stdout.flush().unwrap();
+    }
+
+    pub fn end_message(&self, histories: &[History], message: &str) {
+        let y = 2 + self.try_count as u16;
+        self.view_histories(histories, &[]);
+        execute!(std::io::stdout(),
+            cursor::MoveTo(1, y + 1), terminal::Clear(terminal::ClearType::FromCursorDown),
+            cursor::MoveTo((self.width / 2).saturating_sub(message.chars().count() as u16), y + 2), style::Print(message),
+            cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC"),
+        ).unwrap();
+
+        wait_escape();
+    }
+
+    // 記録したゲームを 1 回答ずつ再生する。→↓ で進む、←↑ で戻る
+    pub fn replay(&self, record: &Record) -> crate::Result<()> {
+        let x = (self.width / 2) - ((5 * self.answer_count) + 1).div_ceil(2) as u16 - 1;
+        let y = 2 + self.try_count as u16;
+        let mut step = 0;
+        loop {
+            let mut stdout = std::io::stdout();
+            queue!(stdout,
+                cursor::Hide,
+                terminal::Clear(terminal::ClearType::All),
+                cursor::MoveTo((self.width / 2) - 10, 1), style::Print("マスター　マインド　リプレイ".yellow()),
+            )?;
+            for i in 1..=self.try_count {
+                queue!(stdout,
+                    cursor::MoveTo(x - 4, 2 + i as u16),
+                    style::Print(format!("{:>2}: {}", i, AnswerView { answer: vec![None; self.answer_count as usize] })),
+                )?;
+            }
+            let elapsed = record.elapsed.as_secs();
+            queue!(stdout,
+                cursor::MoveTo(x - 4, y + 2),
+                style::Print(format!("{}  ルール: {}  シード: {}  時間: {:02}:{:02}  ヒント: {}回",
+                    record::date_string(record.date), record.rule, record.seed, elapsed / 60, elapsed % 60, record.hint_count)),
+                cursor::MoveTo(x - 4, y + 4),
+                style::Print(format!("{}/{}", step, record.histories.len())),
+                cursor::MoveTo(self.width - 16, self.height - 3), style::Print("進む/戻る: →/←"),
+                cursor::MoveTo(self.width - 10, self.height - 2), style::Print("終了: ESC"),
+            )?;
+            // 最後まで進めたら答えと結果
+            if step == record.histories.len() {
+                let result = if record.is_win() { "クリア" } else { "ゲームオーバー" };
+                queue!(stdout, cursor::MoveTo(x + 4, y + 4), style::Print(format!("答え {}  {}", HistoryPins(&record.answer), result)))?;
+            }
+            stdout.flush()?;
+            self.view_histories(&record.histories[..step], &[]);
+
+            loop {
+                let event = event::read()?;
+                match event {
+                    Event::Key(key) if key.kind == event::KeyEventKind::Release => {
+                        match key.code {
+                            KeyCode::Right | KeyCode::Down if step < record.histories.len() => step += 1,
+                            KeyCode::Left | KeyCode::Up if step > 0 => step -= 1,
+                            KeyCode::Home => step = 0,
+                            KeyCode::End => step = record.histories.len(),
+                            KeyCode::Esc => return Ok(()),
+                            _ => continue,
+                        }
+                        break;
+                    },
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+fn wait_escape() {
+    loop {
+        let event = event::read().unwrap();
+        match event {
+            Event::Key(key) if key.kind == event::KeyEventKind::Release && key.code == KeyCode::Esc
+                => return,
+            _ => (),
+        }
+    }
 }
 
-struct HistoryPins<'a>(&'a Vec<Pin>);
+struct HistoryPins<'a>(&'a [Pin]);
 impl<'a> fmt::Display for HistoryPins<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "|")?;
@@ -255,7 +723,7 @@ impl<'a> fmt::Display for HistoryPins<'a> {
     }
 }
 
-struct HistoryHints<'a>(&'a Vec<Hint>);
+struct HistoryHints<'a>(&'a [Hint]);
 impl<'a> fmt::Display for HistoryHints<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.iter().for_each(|hiht| write!(f, "{}", hiht).unwrap());
@@ -296,28 +764,63 @@ impl fmt::Display for KeyItem<u32> {
 struct AnswerWindow {
     position: Position,
     answer: AnswerView,
+    duplicate: bool,    // 同じ色を何度も置けるか
+    undo: Vec<Vec<Option<Pin>>>,    // 入力するたびに、その前の回答を積む
+    redo: Vec<Vec<Option<Pin>>>,    // 元に戻した回答を積む。新しく入力したら捨てる
 }
 
 impl AnswerWindow {
 
+    // 空の回答から
+    fn new(position: Position, answer_count: u32, duplicate: bool) -> Self {
+        Self { position, answer: AnswerView { answer: vec![None; answer_count as usize] }, duplicate, undo: Vec::new(), redo: Vec::new() }
+    }
+
     fn update(&self) {
         let mut stdout = std::io::stdout();
         queue!(stdout,
             cursor::MoveTo(self.position.x, self.position.y),
             style::Print(format!("{}", self.answer))).unwrap();
-        stdout.flush();
+        stdout.flush().unwrap();
     }
 
     fn input_pin(&mut self, pos: usize, pin: Pin) {
-        self.answer.answer = self.answer.answer.iter().enumerate()
+        self.set_pin(pos, pin);
+        self.update();
+    }
+
+    // すべての位置にピンがあれば、その回答
+    fn pins(&self) -> Option<Vec<Pin>> {
+        self.answer.answer.iter().copied().collect()
+    }
+
+    // 同じ色を置けなければ、ほかの位置の同じ色を外す
+    fn set_pin(&mut self, pos: usize, pin: Pin) {
+        let answer: Vec<Option<Pin>> = self.answer.answer.iter().enumerate()
             .map(|(i, a)|
                 if i == pos {
                     Some(pin)
                 } else if let Some(p) = a {
-                    if *p == pin { None }
+                    if *p == pin && !self.duplicate { None }
                     else { Some(*p) }
                 } else { None }).collect();
-        self.update();
+        if answer == self.answer.answer { return; }
+        self.undo.push(mem::replace(&mut self.answer.answer, answer));
+        self.redo.clear();
+    }
+
+    // 戻せなければ false
+    fn undo(&mut self) -> bool {
+        let Some(answer) = self.undo.pop() else { return false };
+        self.redo.push(mem::replace(&mut self.answer.answer, answer));
+        true
+    }
+
+    // やり直せなければ false
+    fn redo(&mut self) -> bool {
+        let Some(answer) = self.redo.pop() else { return false };
+        self.undo.push(mem::replace(&mut self.answer.answer, answer));
+        true
     }
 }
 
@@ -348,32 +851,93 @@ impl<T> SelectGroup<T>
             }
             queue!(stdout, cursor::MoveRight(1)).unwrap();
         };
-        stdout.flush();
+        stdout.flush().unwrap();
     }
 
     fn select(&mut self, s: Option<char>) -> Option<T> {
         if self.selecting == s {
-            return self.select_value();
-        } else {
-            if let Some(key) = s {
-                let find = self.values.iter().find_map(|v| if v.key == key { Some(v.item.clone()) } else { None } );
-                if find.is_some() {
-                    self.selecting = s;
-                    self.update_line();
-                }
-                return find;
-            } else {
-                self.selecting = None;
+            self.select_value()
+        } else if let Some(key) = s {
+            let find = self.values.iter().find_map(|v| if v.key == key { Some(v.item.clone()) } else { None } );
+            if find.is_some() {
+                self.selecting = s;
                 self.update_line();
-                return None;
             }
+            find
+        } else {
+            self.selecting = None;
+            self.update_line();
+            None
         }
     }
 
     fn select_value(&self) -> Option<T> {
         match self.selecting {
-            Some(key) => return self.values.iter().find_map(|v| if v.key == key { Some(v.item.clone()) } else { None } ),
-            None => return None,
+            Some(key) => self.values.iter().find_map(|v| if v.key == key { Some(v.item.clone()) } else { None } ),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::judge_pins;
+
+    #[test]
+    fn answer_undo_redo() {
+        let mut window = AnswerWindow::new(Position { x: 0, y: 0 }, 4, false);
+        assert!(!window.undo());
+        window.set_pin(0, Pin::red());
+        window.set_pin(1, Pin::blue());
+        // 同じ色を置くと、ほかの位置の同じ色が外れる。元に戻せば外れた色も戻る
+        window.set_pin(2, Pin::red());
+        assert_eq!(window.answer.answer, [ None, Some(Pin::blue()), Some(Pin::red()), None ]);
+        assert!(window.undo());
+        assert_eq!(window.answer.answer, [ Some(Pin::red()), Some(Pin::blue()), None, None ]);
+        assert!(window.undo());
+        assert!(window.redo());
+        assert_eq!(window.answer.answer, [ Some(Pin::red()), Some(Pin::blue()), None, None ]);
+
+        // 新しく入力したらやり直せない。変わらない入力は積まない
+        window.set_pin(3, Pin::green());
+        window.set_pin(3, Pin::green());
+        assert!(!window.redo());
+        assert!(window.undo());
+        assert!(window.undo());
+        assert!(window.undo());
+        assert_eq!(window.answer.answer, [ None; 4 ]);
+        assert!(!window.undo());
+    }
+
+    #[test]
+    fn confirm_names() {
+        assert!(Confirm::ALL.iter().all(|confirm| Confirm::from_name(confirm.name()) == Some(*confirm)));
+        assert_eq!(Confirm::from_name("never"), None);
+    }
+
+    #[test]
+    fn confirm_contradiction() {
+        let mut window = AnswerWindow::new(Position { x: 0, y: 0 }, 4, false);
+        assert_eq!(window.pins(), None);
+        for (i, pin) in [ Pin::orange(), Pin::pink(), Pin::green(), Pin::yellow() ].into_iter().enumerate() {
+            window.set_pin(i, pin);
         }
+        let guess = window.pins().unwrap();
+
+        // 答えが 緑 黄 橙 桃 のときのヒント。橙 桃 緑 黄 はどのヒントとも矛盾しない
+        let answer = vec![ Pin::green(), Pin::yellow(), Pin::orange(), Pin::pink() ];
+        let clue = vec![ Pin::orange(), Pin::yellow(), Pin::green(), Pin::pink() ];
+        let mut histories = vec![ History { hints: judge_pins(&answer, &clue), pins: clue } ];
+        assert!(!Confirm::Contradiction.asks(&histories, &guess));
+        assert!(Confirm::Always.asks(&histories, &guess));
+
+        // 赤が入っていないとわかっているのに赤を置いた回答は矛盾する
+        histories.push(History { hints: judge_pins(&answer, &[ Pin::red(); 4 ]), pins: vec![ Pin::red(); 4 ] });
+        window.set_pin(0, Pin::red());
+        let guess = window.pins().unwrap();
+        assert!(Confirm::Contradiction.asks(&histories, &guess));
+        assert!(!Confirm::Off.asks(&histories, &guess));
+        assert!(!Confirm::Quick.asks(&histories, &guess));
     }
 }
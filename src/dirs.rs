@@ -0,0 +1,20 @@
+use std::env;
+use std::path::PathBuf;
+
+// キャッシュを置くディレクトリ(消えても作り直せるもの)
+pub fn cache_dir() -> PathBuf {
+    env::var_os("XDG_CACHE_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(env::temp_dir)
+        .join("master-mind")
+}
+
+// 中断したゲームや記録を置くディレクトリ
+pub fn data_dir() -> PathBuf {
+    env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .unwrap_or_else(env::temp_dir)
+        .join("master-mind")
+}
@@ -0,0 +1,174 @@
+use std::fs;
+
+use itertools::Itertools;
+
+use crate::{Args, Error, preset_stats};
+use crate::record::{self, Record};
+use crate::stats::{self, Stats};
+
+// 出力できる形式
+pub const FORMATS: [&str; 2] = [ "json", "csv" ];
+
+// 表の 1 つの値
+enum Value {
+    Text(String),
+    Int(u64),
+    Float(f64),
+    Null,
+}
+
+impl Value {
+
+    // 区切りや引用符を含む文字列だけ引用符で囲む
+    fn csv(&self) -> String {
+        match self {
+            Value::Text(text) if text.contains([ ',', '"', '\n' ]) => format!("\"{}\"", text.replace('"', "\"\"")),
+            Value::Text(text) => text.clone(),
+            Value::Int(n) => n.to_string(),
+            Value::Float(f) => format!("{:.4}", f),
+            Value::Null => String::new(),
+        }
+    }
+
+    fn json(&self) -> String {
+        match self {
+            Value::Text(text) => {
+                let escaped: String = text.chars().map(|ch| match ch {
+                    '"' => String::from("\\\""),
+                    '\\' => String::from("\\\\"),
+                    ch if ch.is_control() => format!("\\u{:04x}", ch as u32),
+                    ch => ch.to_string(),
+                }).collect();
+                format!("\"{}\"", escaped)
+            },
+            Value::Int(n) => n.to_string(),
+            Value::Float(f) => format!("{:.4}", f),
+            Value::Null => String::from("null"),
+        }
+    }
+}
+
+// 見出しと行。CSV は 1 行目が見出し、JSON は 1 行を 1 つのオブジェクトにした配列
+struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+
+    fn csv(&self) -> String {
+        let mut lines = vec![ self.header.iter().map(|name| Value::Text(name.clone()).csv()).join(",") ];
+        lines.extend(self.rows.iter().map(|row| row.iter().map(Value::csv).join(",")));
+        lines.join("\n") + "\n"
+    }
+
+    fn json(&self) -> String {
+        let mut rows = self.rows.iter().map(|row| {
+            let fields = self.header.iter().zip(row)
+                .map(|(name, value)| format!("{}: {}", Value::Text(name.clone()).json(), value.json()))
+                .join(", ");
+            format!("  {{ {} }}", fields)
+        });
+        format!("[\n{}\n]\n", rows.join(",\n"))
+    }
+}
+
+// 記録したゲームの、1 回答ごとの行
+// 記録には回答ごとの時刻がないので、日時はどの行もゲームが終わった日時 (game_date, game_time)
+fn games_table(records: &[(String, Record)]) -> Table {
+    let header = [ "game", "game_date", "game_time", "rule", "seed", "result", "guess", "colors", "hit", "blow" ];
+    let rows = records.iter()
+        .flat_map(|(game, record)| record.histories.iter().enumerate().map(move |(i, history)| {
+            let (hit, blow) = history.hit_blow();
+            vec![
+                Value::Text(game.clone()), Value::Int(record.date), Value::Text(record::date_string(record.date)),
+                Value::Text(record.rule.clone()), Value::Int(record.seed),
+                Value::Text(String::from(if record.is_win() { "win" } else { "lose" })),
+                Value::Int(i as u64 + 1), Value::Text(history.pins.iter().map(|pin| pin.name()).join(" ")),
+                Value::Int(hit as u64), Value::Int(blow as u64),
+            ]
+        }))
+        .collect();
+    Table { header: header.map(String::from).to_vec(), rows }
+}
+
+// プリセットのルールごとの成績の行。当てた回数ごとの数は、そのルールにない回数なら空
+fn stats_table(stats: &[(&str, Stats)]) -> Table {
+    let max = stats.iter().map(|(_, stats)| stats.histogram.len()).max().unwrap_or(0);
+    let mut header: Vec<String> = [ "rule", "games", "wins", "win_rate", "streak", "best_streak" ].map(String::from).to_vec();
+    header.extend((1..=max).map(|count| count.to_string()));
+    let rows = stats.iter()
+        .map(|(name, stats)| {
+            let mut row = vec![
+                Value::Text(name.to_string()), Value::Int(stats.games as u64), Value::Int(stats.wins as u64),
+                Value::Float(stats.win_rate()), Value::Int(stats.streak as u64), Value::Int(stats.best_streak as u64),
+            ];
+            row.extend((0..max).map(|i| stats.histogram.get(i).map_or(Value::Null, |count| Value::Int(*count as u64))));
+            row
+        })
+        .collect();
+    Table { header, rows }
+}
+
+fn write(args: &Args, table: &Table) -> crate::Result<()> {
+    let format = args.format.as_deref().unwrap_or("json");
+    if !FORMATS.contains(&format) {
+        return Err(Box::new(Error::Args { arg: format.to_string() }));
+    }
+    let text = if format == "csv" { table.csv() } else { table.json() };
+    match &args.output {
+        Some(path) => fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+// 記録したすべてのゲームを出力する。読めない記録は飛ばして、そのファイルを標準エラーに知らせる
+pub fn run_games(args: &Args) -> crate::Result<()> {
+    let records: Vec<(String, Record)> = record::list().into_iter()
+        .filter_map(|path| match Record::load(&path) {
+            Ok(record) => {
+                let game = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+                Some((game, record))
+            },
+            Err(_) => {
+                eprintln!("skipped broken record file: {}", path.display());
+                None
+            },
+        })
+        .collect();
+    write(args, &games_table(&records))
+}
+
+// ルールごとの成績を出力する
+pub fn run_stats(args: &Args) -> crate::Result<()> {
+    write(args, &stats_table(&preset_stats(&stats::load()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::{Pin, histories};
+
+    #[test]
+    fn export_games() {
+        let answer = vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let histories = histories(&answer, vec![
+            vec![ Pin::pink(), Pin::orange(), Pin::red(), Pin::blue() ],
+            vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ],
+        ]);
+        let record = Record { date: 0, ..Record::new("default", 42, Duration::from_secs(83), 1, &answer, &histories) };
+        let table = games_table(&[ (String::from("game,1"), record) ]);
+
+        assert_eq!(table.csv(), "game,game_date,game_time,rule,seed,result,guess,colors,hit,blow\n\
+            \"game,1\",0,1970-01-01 00:00,default,42,win,1,pink orange red blue,0,2\n\
+            \"game,1\",0,1970-01-01 00:00,default,42,win,2,red blue green yellow,4,0\n");
+        let json = table.json();
+        assert!(json.starts_with("[\n  { \"game\": \"game,1\", \"game_date\": 0, "));
+        assert!(json.contains("\"colors\": \"red blue green yellow\", \"hit\": 4, \"blow\": 0 }\n]"));
+
+        assert_eq!(Value::Text(String::from("a\"b\n")).json(), "\"a\\\"b\\u000a\"");
+        assert_eq!(Value::Null.csv(), "");
+    }
+}
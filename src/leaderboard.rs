@@ -0,0 +1,144 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::Error;
+use crate::dirs::data_dir;
+
+// 勝ったゲームの点数
+//  残った回数 (当てた回も含む) ごとに 1000 点
+//  かかった秒数だけ引く (10 分まで)
+//  ヒントを使うたびに 300 点引く
+pub fn score(try_count: u32, guesses: usize, elapsed: Duration, hint_count: u32) -> u32 {
+    let base = (try_count as usize + 1).saturating_sub(guesses) as u32 * 1000;
+    base.saturating_sub(elapsed.as_secs().min(600) as u32).saturating_sub(hint_count * 300)
+}
+
+// ランキングの 1 行
+#[derive(Clone, PartialEq, Debug)]
+pub struct Entry {
+    pub rule: String,   // ルールの名前
+    pub name: String,   // プレイヤーの名前
+    pub score: u32,
+    pub guesses: usize,
+    pub elapsed: Duration,
+    pub hint_count: u32,
+    pub date: u64,      // 登録した日時 (UNIX 時間の秒)
+}
+
+impl Entry {
+
+    const HEADER: &'static str = "rule,name,score,guesses,seconds,hints,date";
+
+    pub fn new(rule: &str, name: &str, score: u32, guesses: usize, elapsed: Duration, hint_count: u32) -> Self {
+        let date = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+        Self { rule: rule.to_string(), name: name.to_string(), score, guesses, elapsed, hint_count, date }
+    }
+
+    fn to_line(&self) -> String {
+        format!("{},{},{},{},{},{},{}", self.rule, self.name, self.score, self.guesses, self.elapsed.as_secs(), self.hint_count, self.date)
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [ rule, name, score, guesses, seconds, hints, date ] = fields[..] else { return None };
+        Some(Self {
+            rule: rule.to_string(), name: name.to_string(), score: score.parse().ok()?, guesses: guesses.parse().ok()?,
+            elapsed: Duration::from_secs(seconds.parse().ok()?), hint_count: hints.parse().ok()?, date: date.parse().ok()?,
+        })
+    }
+}
+
+// ルールごとに上位だけを残したランキング
+#[derive(Default)]
+pub struct Leaderboard {
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+
+    // ルールごとに残す数
+    pub const TOP: usize = 10;
+    // 名前の最大の文字数
+    pub const NAME_MAX: usize = 16;
+
+    pub fn path() -> PathBuf {
+        data_dir().join("leaderboard.csv")
+    }
+
+    // まだファイルがなければ空
+    pub fn load() -> crate::Result<Self> {
+        let path = Self::path();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(Box::new(e)),
+        };
+        let error = || Error::Leaderboard { path: path.display().to_string() };
+        let mut lines = text.lines();
+        if lines.next() != Some(Entry::HEADER) { return Err(Box::new(error())); }
+        let entries = lines.map(Entry::parse).collect::<Option<_>>().ok_or_else(error)?;
+        Ok(Self { entries })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(data_dir())?;
+        let lines: Vec<String> = self.entries.iter().map(Entry::to_line).collect();
+        fs::write(Self::path(), format!("{}\n{}\n", Entry::HEADER, lines.join("\n")))
+    }
+
+    // 点数の高い順。同じ点数なら先に登録したもの
+    pub fn top(&self, rule: &str) -> Vec<&Entry> {
+        let mut entries: Vec<&Entry> = self.entries.iter().filter(|entry| entry.rule == rule).collect();
+        entries.sort_by(|a, b| b.score.cmp(&a.score).then(a.date.cmp(&b.date)));
+        entries
+    }
+
+    // 登録して順位 (0 から) を返す。上位に入らなければ None
+    pub fn insert(&mut self, entry: Entry) -> Option<usize> {
+        let rule = entry.rule.clone();
+        self.entries.push(entry.clone());
+        let top: Vec<Entry> = self.top(&rule).into_iter().take(Self::TOP).cloned().collect();
+        let rank = top.iter().position(|e| *e == entry);
+        self.entries.retain(|e| e.rule != rule);
+        self.entries.extend(top);
+        rank
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaderboard_insert() {
+        // 少ない回数、短い時間、ヒントなしほど高い
+        let minute = Duration::from_secs(60);
+        assert_eq!(score(10, 4, minute, 0), 6940);
+        assert!(score(10, 4, minute, 0) > score(10, 5, minute, 0));
+        assert!(score(10, 4, minute, 0) > score(10, 4, minute * 2, 0));
+        assert!(score(10, 4, minute, 0) > score(10, 4, minute, 1));
+        assert_eq!(score(10, 10, minute * 60, 10), 0);
+
+        let mut board = Leaderboard::default();
+        for i in 0..Leaderboard::TOP as u32 {
+            let entry = Entry { date: i as u64, ..Entry::new("default", "alice", 1000 + i, 5, minute, 0) };
+            assert_eq!(board.insert(entry), Some(0));
+        }
+        assert_eq!(board.insert(Entry::new("default", "bob", 500, 8, minute, 0)), None);
+        assert_eq!(board.insert(Entry::new("default", "carol", 1005, 5, minute, 0)), Some(5));
+        assert_eq!(board.insert(Entry::new("classic", "dave", 10, 9, minute, 0)), Some(0));
+
+        let top = board.top("default");
+        assert_eq!(top.len(), Leaderboard::TOP);
+        assert_eq!(top[0].score, 1009);
+        assert_eq!(top[5].name, "carol");
+        assert!(top.iter().all(|entry| entry.score > 1000));
+        assert_eq!(board.top("classic").len(), 1);
+
+        for entry in &board.entries {
+            assert_eq!(Entry::parse(&entry.to_line()).as_ref(), Some(entry));
+        }
+    }
+}
@@ -1,16 +1,42 @@
 use std::error;
 use std::fmt;
 use std::collections::HashSet;
+use std::slice;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use itertools::Itertools;
 //use strum::IntoEnumIterator;
 //use strum_macros::EnumIter;
-use rand::thread_rng;
-use rand::seq::IteratorRandom;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::rngs::StdRng;
 use crossterm::style::Color;
 
+mod analysis;
+mod args;
+use args::{Args, Command};
+mod bench;
+mod book;
+mod code;
+use code::{Code, CodeSpace};
 mod console_view;
-use console_view::ConsoleView;
+mod dirs;
+mod export;
+mod leaderboard;
+use leaderboard::Leaderboard;
+use console_view::{ConsoleView, EndAction, Input};
+mod puzzle;
+use puzzle::Puzzle;
+mod record;
+use record::Record;
+mod save;
+use save::SavedGame;
+mod solver;
+mod stats;
+use stats::{GameResult, Stats};
+mod table;
+mod tree;
 
 //#[derive(EnumIter, Clone, PartialEq, Eq, Hash, Debug)]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -25,12 +51,39 @@ impl Pin {
     fn yellow() -> Pin { Pin { color: Color::Yellow } }
     fn pink() -> Pin { Pin { color: Color::Rgb { r:247, g:155, b:185 } } }
     fn orange() -> Pin { Pin { color: Color::Rgb { r:255, g:165, b:0 } } }
+    fn purple() -> Pin { Pin { color: Color::Magenta } }
+    fn white() -> Pin { Pin { color: Color::White } }
+
+    // すべての色と、ファイルなどに書くときの名前
+    fn named() -> [(Pin, &'static str); 8] {
+        [ (Pin::red(), "red"), (Pin::green(), "green"), (Pin::blue(), "blue"), (Pin::yellow(), "yellow"),
+          (Pin::pink(), "pink"), (Pin::orange(), "orange"), (Pin::purple(), "purple"), (Pin::white(), "white") ]
+    }
+
+    fn name(&self) -> &'static str {
+        Self::named().into_iter().find(|(pin, _)| pin == self).map_or("unknown", |(_, name)| name)
+    }
+
+    fn from_name(name: &str) -> Option<Pin> {
+        Self::named().into_iter().find(|(_, n)| *n == name).map(|(pin, _)| pin)
+    }
 }
 
 #[derive(Debug)]
 pub enum Error {
     AnswerNew { pins_len: usize, count:usize },   // 答えを生成できなかった
     EndOfEscape,
+    Args { arg: String },   // コマンドライン引数が不正
+    NoCandidate,            // ヒントと矛盾しない答えがない
+    Cache { path: String }, // キャッシュファイルが壊れている
+    NoSave,                 // 中断したゲームがない
+    Save { path: String },  // 中断したゲームのファイルが壊れている
+    Record { path: String },    // 記録のファイルが壊れている
+    NoRecord,               // 記録がない
+    Stats { path: String }, // 成績のファイルが壊れている
+    Leaderboard { path: String },   // ランキングのファイルが壊れている
+    Puzzle { path: String },    // パズルのファイルが壊れているか、答えがただ 1 つに決まらない
+    Difficulty { difficulty: String },  // このルールでは指定の難しさのパズルを作れなかった
 }
 
 impl fmt::Display for Error {
@@ -38,6 +91,17 @@ impl fmt::Display for Error {
         match self {
             Error::AnswerNew { pins_len, count } => write!(f, "Answer::new() error. pins.len:{}, count:{}", pins_len, count),
             Error::EndOfEscape => write!(f, "End of Escape"),
+            Error::Args { arg } => write!(f, "invalid argument: {}", arg),
+            Error::NoCandidate => write!(f, "no candidate is consistent with the hints"),
+            Error::Cache { path } => write!(f, "broken cache file: {}", path),
+            Error::NoSave => write!(f, "no saved game"),
+            Error::Save { path } => write!(f, "broken save file: {}", path),
+            Error::Record { path } => write!(f, "broken record file: {}", path),
+            Error::NoRecord => write!(f, "no game record"),
+            Error::Stats { path } => write!(f, "broken stats file: {}", path),
+            Error::Leaderboard { path } => write!(f, "broken leaderboard file: {}", path),
+            Error::Puzzle { path } => write!(f, "invalid puzzle file (must have exactly one solution): {}", path),
+            Error::Difficulty { difficulty } => write!(f, "could not generate a puzzle of difficulty {} for this rule", difficulty),
         }
     }
 }
@@ -46,98 +110,537 @@ impl error::Error for Error {}
 
 pub type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
 
-#[derive(PartialEq, PartialOrd, Debug)]
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
 enum Hint { Blow, Hit, None, }
 
 #[derive(Debug)]
 struct Answer {
     answer: Vec<Pin>,
+    duplicate: bool,    // 同じ色を何度も回答できるか
 }
 
 impl Answer {
 
-    fn new(pins: &HashSet<Pin>, count: usize) -> Result<Answer> {
-        let answer = pins.clone().into_iter().permutations(count).choose(&mut thread_rng())
-            .ok_or(Error::AnswerNew { pins_len: pins.len(), count} )?;
-        Ok(Answer { answer })
+    fn new(rule: &Rule, rng: &mut StdRng) -> Result<Answer> {
+        // シードから同じ答えを再現できるように、答えの番号を選ぶ
+        let space = rule.code_space();
+        let error = Error::AnswerNew { pins_len: rule.pins.len(), count: rule.answer_count as usize };
+        if space.count() == 0 { return Err(Box::new(error)); }
+        let code = space.unrank(rng.gen_range(0..space.count())).ok_or(error)?;
+        Ok(Answer { answer: code.to_pins(&rule.palette()), duplicate: rule.duplicate })
     }
 
     fn judge(&self, pins: &[Pin]) -> Option<Vec<Hint>> {
         // 数のチェック
         if pins.len() != self.answer.len() { return None; }
 
+        // 重複チェック
         let mut duplicate_check = HashSet::new();
-        let mut hints = vec![];
-        for (idx, pin) in pins.iter().enumerate() {
-            // 重複チェック
-            if !duplicate_check.insert(pin) { return None; }
-            for (a_idx, a_pin) in self.answer.iter().enumerate() {
-                if pin == a_pin {
-                    // 答え(answer)に同じ色があり、位置もあっていれば Hit、位置が違えば Blow
-                    hints.push(if idx == a_idx { Hint::Hit } else { Hint::Blow });
-                    break;
-                }
-            }
-            // 答え(answer)に同じ色がなければ None
-            if hints.len() == idx { hints.push(Hint::None) };
-        }
+        if !self.duplicate && !pins.iter().all(|pin| duplicate_check.insert(pin)) { return None; }
 
-        // ヒントをソートして返す(Blow->Hit->None)
-        hints.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        Some(hints)
+        Some(judge_pins(&self.answer, pins))
     }
 }
+
+// 答え(answer)に対して pins を回答したときの (Hit 数, Blow 数)
+// 重複ありのルールでも使えるよう、色ごとに一致した数の合計から Hit を引いたものを Blow とする
+fn hit_blow(answer: &[Pin], pins: &[Pin]) -> (usize, usize) {
+    let hit = answer.iter().zip(pins).filter(|(a, p)| a == p).count();
+    let common: usize = pins.iter().enumerate()
+        .filter(|(idx, pin)| !pins[..*idx].contains(pin))
+        .map(|(_, pin)| answer.iter().filter(|a| *a == pin).count().min(pins.iter().filter(|p| *p == pin).count()))
+        .sum();
+    (hit, common - hit)
+}
+
+// ヒントをソートして返す(Blow->Hit->None)
+fn judge_pins(answer: &[Pin], pins: &[Pin]) -> Vec<Hint> {
+    let (hit, blow) = hit_blow(answer, pins);
+    hints(hit, blow, pins.len())
+}
+
+// テスト用: 答えに対する回答の履歴
+#[cfg(test)]
+fn histories(answer: &[Pin], guesses: Vec<Vec<Pin>>) -> Vec<History> {
+    guesses.into_iter().map(|pins| History { hints: judge_pins(answer, &pins), pins }).collect()
+}
+
+// Hit と Blow の数から、ソート済みのヒントを作る
+fn hints(hit: usize, blow: usize, count: usize) -> Vec<Hint> {
+    let mut hints = Vec::with_capacity(count);
+    hints.extend((0..blow).map(|_| Hint::Blow));
+    hints.extend((0..hit).map(|_| Hint::Hit));
+    hints.extend((hit + blow..count).map(|_| Hint::None));
+    hints
+}
+
 struct Rule {
     pins: HashSet<Pin>,
     answer_count: u32,
     try_count: u32,
+    duplicate: bool,    // 同じ色を何度も使えるか
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        let pins = HashSet::from( [ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow(), Pin::pink(), Pin::orange() ] );
+        Rule { pins, answer_count: 4, try_count: 10, duplicate: false }
+    }
 }
 
+// 選べるルールの名前
+const RULES: [&str; 3] = [ "default", "classic", "super" ];
+
+impl Rule {
+    // ピンを並べたもの。Code の色の番号はこの位置
+    fn palette(&self) -> Vec<Pin> {
+        self.pins.iter().copied().sorted().collect()
+    }
+
+    // このルールで置ける回答か(ピンの数と色だけ。重複は調べない)
+    fn is_code(&self, pins: &[Pin]) -> bool {
+        pins.len() == self.answer_count as usize && pins.iter().all(|pin| self.pins.contains(pin))
+    }
+
+    fn preset(name: &str) -> Option<Rule> {
+        let six = [ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow(), Pin::pink(), Pin::orange() ];
+        match name {
+            // 6色 4つ 重複なし
+            "default" => Some(Rule::default()),
+            // 6色 4つ 重複あり (ボードゲームのルール)
+            "classic" => Some(Rule { pins: HashSet::from(six), answer_count: 4, try_count: 10, duplicate: true }),
+            // 8色 5つ 重複あり
+            "super" => {
+                let pins = six.into_iter().chain([ Pin::purple(), Pin::white() ]).collect();
+                Some(Rule { pins, answer_count: 5, try_count: 12, duplicate: true })
+            },
+            _ => None,
+        }
+    }
+
+    // テスト用: ルールで作れる答えをすべて、ピンの並びで列挙する
+    #[cfg(test)]
+    fn codes(&self) -> Vec<Vec<Pin>> {
+        let palette = self.palette();
+        self.code_space().iter().map(|code| code.to_pins(&palette)).collect()
+    }
+
+    fn code_space(&self) -> CodeSpace {
+        CodeSpace::new(self)
+    }
+}
+
+#[derive(Clone)]
 struct History {
     pins: Vec<Pin>,
     hints: Vec<Hint>,
 }
 
-pub fn start() -> Result<()> {
+impl History {
+    // code が答えだった場合に、この履歴と同じヒントになるか
+    fn is_consistent(&self, code: &[Pin]) -> bool {
+        hit_blow(code, &self.pins) == self.hit_blow()
+    }
+
+    fn hit_blow(&self) -> (usize, usize) {
+        (self.hints.iter().filter(|h| **h == Hint::Hit).count(), self.hints.iter().filter(|h| **h == Hint::Blow).count())
+    }
+}
+
+// 回答ごとに、それまでのヒントすべてと矛盾しない答えがいくつ残っていたか
+fn remaining_counts(rule: &Rule, histories: &[History]) -> Vec<usize> {
+    let palette = rule.palette();
+    let mut candidates: Vec<Code> = rule.code_space().iter().collect();
+    histories.iter()
+        .map(|history| {
+            candidates = solver::candidates(&candidates, &palette, slice::from_ref(history));
+            candidates.len()
+        })
+        .collect()
+}
+
+// ゲーム終了時に表示する内容
+struct Summary<'a> {
+    rule: &'a str,      // ルールの名前
+    answer: &'a [Pin],
+    elapsed: Duration,
+    seed: u64,
+    remainings: Vec<usize>,
+    hint_count: u32,
+    score: Option<u32>, // 勝ったときの点数
+}
+
+pub fn start(args: impl Iterator<Item = String>) -> Result<()> {
+    let args = Args::parse(args)?;
+    match args.command {
+        Command::Play => play(&args),
+        Command::Solve => solve(&args),
+        Command::Bench => bench::run(&args),
+        Command::Book => book::run(&args),
+        Command::Tree => tree::run(&args),
+        Command::Replay => replay(&args),
+        Command::Stats => view_stats(),
+        Command::Puzzle => play_puzzle(&args),
+        Command::Puzzles => puzzle::run_batch(&args),
+        Command::Export => export::run_games(&args),
+        Command::ExportStats => export::run_stats(&args),
+        Command::Breaker => breaker(&args),
+        Command::Assist => assist(&args),
+    }
+}
 
+fn play(args: &Args) -> Result<()> {
+
+    // 中断したゲームを続けるなら、ルールやシードもそのゲームのもの
+    let saved = if args.resume { Some(save::load()?) } else { None };
+    let rule_name = saved.as_ref().map_or(args.rule.clone(), |saved| saved.rule.clone());
     // ルール
-    let pins = HashSet::from( [ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow(), Pin::pink(), Pin::orange() ] );
-    let rule = Rule { pins, answer_count: 4, try_count: 10 };
-    let pins: Vec<&Pin> = rule.pins.iter().collect();
+    let rule = Rule::preset(&rule_name).ok_or(Error::Args { arg: rule_name.clone() })?;
     // 答え
-    let answer = Answer::new(&rule.pins, rule.answer_count as usize)?;
-    println!("answer: {:?}", answer);
+    let seed = saved.as_ref().map(|saved| saved.seed).or(args.seed).unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut answer = Answer::new(&rule, &mut rng)?;
+    if let Some(saved) = &saved {
+        answer.answer = saved.answer.clone();
+    }
+    // ヒント用のソルバー
+    let mut solver = args.solver(&rule, rng.gen())?;
 
-    let mut view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count);
+    let mut view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count, rule.duplicate);
+    view.set_confirm(args.confirm);
+    solver.set_progress(view.progress());
     view.update()?;
+    let started = Instant::now();
+    // 中断するまでにかかっていた時間
+    let elapsed = saved.as_ref().map_or(Duration::ZERO, |saved| saved.elapsed);
 
     // 最大回数まで
-    let mut histories = Vec::new();
-    while histories.len() < rule.try_count as usize {
+    let mut histories: Vec<History> = saved.as_ref().map_or(Vec::new(), |saved| saved.guesses.iter()
+        .map(|pins| History { hints: judge_pins(&answer.answer, pins), pins: pins.clone() })
+        .collect());
+    let mut remainings = if args.remaining { remaining_counts(&rule, &histories) } else { Vec::new() };
+    let mut hint_count = saved.as_ref().map_or(0, |saved| saved.hint_count);
+    // 練習なら回答を取り消せる。取り消したらランキングにも成績にも残さない
+    let practice = saved.as_ref().map_or(args.practice, |saved| saved.practice);
+    let mut unranked = saved.as_ref().is_some_and(|saved| saved.unranked);
+    view.set_practice(practice);
+    let mut prefill = None;
+    let mut is_win = false;
+    while histories.len() < rule.try_count as usize && !is_win {
 
         // 現在の状況を表示する
         // 入力を待つ
         // 入力を判定する
-        let pins = view.wait_input(&histories)?;
+        let pins = match view.wait_input(&histories, &remainings, prefill.as_deref())? {
+            Input::Answer(pins) => pins,
+            // ヒントを使うたびに数えておく
+            Input::Hint => {
+                hint_count += 1;
+                prefill = solver.next_guess(&histories).map(|pins| pins.into_iter().map(Some).collect());
+                continue;
+            },
+            Input::TakeBack => {
+                take_back(&mut histories, &mut unranked);
+                if args.remaining {
+                    remainings = remaining_counts(&rule, &histories);
+                }
+                prefill = None;
+                view.update()?;
+                continue;
+            },
+            Input::Escape(row) => match view.ask_save()? {
+                Some(true) => {
+                    let game = SavedGame {
+                        rule: rule_name, seed, answer: answer.answer, guesses: histories.into_iter().map(|history| history.pins).collect(),
+                        elapsed: elapsed + started.elapsed(), hint_count, practice, unranked,
+                    };
+                    save::save(&game)?;
+                    drop(view);
+                    println!("保存しました。続けるには --resume をつけてください");
+                    return Ok(());
+                },
+                Some(false) => return Err(Box::new(Error::EndOfEscape)),
+                // 続けるなら、入力途中の回答から
+                None => {
+                    prefill = Some(row);
+                    continue;
+                },
+            },
+        };
+        prefill = None;
         match answer.judge(&pins) {
             // 結果あり
             Some(hints) => {
-                let is_win = hints.iter().all(|h| h == &Hint::Hit);
+                is_win = hints.iter().all(|h| h == &Hint::Hit);
                 histories.push(History { pins, hints });
-                if is_win {
-                    view.win(&histories);
-                    return Ok(())
+                if args.remaining {
+                    remainings = remaining_counts(&rule, &histories);
                 }
             },
             // 結果なし（回答のピンが足りない or 重複がある）
             None => continue,
         };
     }
-    view.game_over(&histories);
+
+    let elapsed = elapsed + started.elapsed();
+    let score = ranked_score(&rule, &histories, is_win, unranked, elapsed, hint_count);
+    let summary = Summary { rule: &rule_name, answer: &answer.answer, elapsed, seed, remainings: remaining_counts(&rule, &histories), hint_count, score };
+    // 記録と成績を残す(書けなくてもゲームは続ける)
+    let record = Record::new(&rule_name, seed, elapsed, hint_count, &answer.answer, &histories);
+    let _ = record.write();
+    if !unranked {
+        let _ = stats::append(&GameResult {
+            date: record.date, rule: rule_name.clone(), win: is_win, guesses: histories.len(), elapsed, hint_count,
+        });
+    }
+    let action = if is_win {
+        // ランキングは点数があるときだけ読む。読めなければ登録しない(壊れたファイルは上書きしない)
+        let mut board = score.and_then(|_| Leaderboard::load().ok());
+        let action = view.win(&histories, &summary, board.as_mut());
+        if let Some(board) = board {
+            let _ = board.save();
+        }
+        action
+    } else {
+        view.game_over(&histories, &summary)
+    };
+    match action {
+        EndAction::Analysis => {
+            let analyses = analysis::analyze(&rule, &histories, solver.as_mut());
+            view.view_analysis(&histories, &analyses);
+        },
+        EndAction::Stats => view.view_stats(&preset_stats(&stats::load()?)),
+        EndAction::Quit => (),
+    }
+
+    // 結果を共有する文章は、画面を戻してから出力する
+    drop(view);
+    let share = record.share_text(rule.try_count);
+    match &args.share {
+        Some(path) => fs::write(path, share + "\n")?,
+        None => println!("{}", share),
+    }
 
     Ok(())
 }
 
+// プリセットのルールごとの成績
+fn preset_stats(results: &[GameResult]) -> Vec<(&'static str, Stats)> {
+    RULES.iter()
+        .filter_map(|name| Rule::preset(name).map(|rule| (*name, Stats::new(results, name, rule.try_count))))
+        .collect()
+}
+
+// 成績の画面
+fn view_stats() -> Result<()> {
+    let stats = preset_stats(&stats::load()?);
+    let rule = Rule::default();
+    let view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count, rule.duplicate);
+    view.view_stats(&stats);
+    Ok(())
+}
+
+// 記録したゲームを再生する。指定がなければ最後のゲーム
+fn replay(args: &Args) -> Result<()> {
+    let path = match &args.record {
+        Some(path) => PathBuf::from(path),
+        None => record::list().pop().ok_or(Error::NoRecord)?,
+    };
+    let record = Record::load(&path)?;
+    let rule = Rule::preset(&record.rule)
+        .filter(|rule| record.is_valid(rule))
+        .ok_or(Error::Record { path: path.display().to_string() })?;
+
+    let view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count, rule.duplicate);
+    view.replay(&record)
+}
+
+// 推理パズル。手がかりだけで答えを推理して、できるだけ少ない回答で当てる
+fn play_puzzle(args: &Args) -> Result<()> {
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let (puzzle, rule) = match &args.puzzle {
+        Some(path) => Puzzle::load(Path::new(path), args.number)?,
+        None => {
+            let rule = args.rule()?;
+            let mut rng = StdRng::seed_from_u64(seed);
+            let puzzle = match args.difficulty {
+                Some(difficulty) => puzzle::generate_graded(&args.rule, &rule, difficulty, &mut rng)?,
+                None => puzzle::generate(&args.rule, &rule, &mut rng),
+            };
+            (puzzle, rule)
+        },
+    };
+    // 出力先があれば、パズルを書くだけ
+    if let Some(path) = &args.output {
+        puzzle.save(Path::new(path))?;
+        return Ok(());
+    }
+    let answer = Answer { answer: puzzle.answer(&rule).ok_or(Error::NoCandidate)?, duplicate: rule.duplicate };
+
+    let mut view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count, rule.duplicate);
+    view.set_confirm(args.confirm);
+    // パズルではヒントは使えない
+    view.set_hints(false);
+    view.update()?;
+
+    let mut histories = puzzle.clues.clone();
+    let mut is_win = false;
+    while histories.len() < rule.try_count as usize && !is_win {
+        let pins = match view.wait_input(&histories, &[], None)? {
+            Input::Answer(pins) => pins,
+            Input::Hint | Input::TakeBack => continue,
+            Input::Escape(_) => return Err(Box::new(Error::EndOfEscape)),
+        };
+        if let Some(hints) = answer.judge(&pins) {
+            is_win = hints.iter().all(|h| h == &Hint::Hit);
+            histories.push(History { pins, hints });
+        }
+    }
+
+    // 答えを入れた回は数えない
+    let extra = histories.len() - puzzle.clues.len() - is_win as usize;
+    let mut message = if is_win {
+        format!("正解！  余分な回答: {} 回", extra)
+    } else {
+        format!("残念  答え {}", answer.answer.iter().join(" "))
+    };
+    message += &format!("  難しさ: {}", puzzle.grade(&rule).difficulty.name());
+    if args.puzzle.is_none() {
+        message += &format!("  シード: {}", seed);
+    }
+    view.end_message(&histories, &message);
+
+    Ok(())
+}
+
+// ソルバーに答えを当てさせて、経過を表示する
+fn solve(args: &Args) -> Result<()> {
+    let rule = args.rule()?;
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let answer = Answer::new(&rule, &mut rng)?;
+    let mut solver = args.solver(&rule, rng.gen())?;
+    let number = Code::from_pins(&answer.answer, &rule.palette()).and_then(|code| rule.code_space().rank(code));
+    println!("rule: {}  solver: {}  seed: {}  answer: #{}", args.rule, args.solver, seed, number.unwrap_or_default());
+
+    let mut histories = Vec::new();
+    while histories.len() < rule.try_count as usize {
+        let pins = solver.next_guess(&histories).ok_or(Error::NoCandidate)?;
+        let hints = judge_pins(&answer.answer, &pins);
+        print!("{:>2}: {} {}", histories.len() + 1, pins.iter().join(" "), hints.iter().join(""));
+
+        let is_win = hints.iter().all(|h| h == &Hint::Hit);
+        histories.push(History { pins, hints });
+        if args.remaining {
+            print!("  残り {}", remaining_counts(&rule, &histories).last().unwrap_or(&0));
+        }
+        println!();
+        if is_win {
+            println!("{} 回で正解", histories.len());
+            return Ok(());
+        }
+    }
+    println!("{} 回で当てられなかった", rule.try_count);
+
+    Ok(())
+}
+
+// 回答を 1 つ取り消す。取り消せたときだけ、ランキングにも成績にも残さないゲームにする
+fn take_back(histories: &mut Vec<History>, unranked: &mut bool) {
+    if histories.pop().is_some() {
+        *unranked = true;
+    }
+}
+
+// ランキングに載せる点数。勝って、取り消しのないゲームだけ
+fn ranked_score(rule: &Rule, histories: &[History], is_win: bool, unranked: bool, elapsed: Duration, hint_count: u32) -> Option<u32> {
+    (is_win && !unranked).then(|| leaderboard::score(rule.try_count, histories.len(), elapsed, hint_count))
+}
+
+// どの返答が間違っているか
+fn contradiction_message(rule: &Rule, histories: &[History]) -> String {
+    let codes: Vec<Code> = rule.code_space().iter().collect();
+    match solver::contradictions(&codes, &rule.palette(), histories)[..] {
+        [] => String::from("2つ以上の返答が間違っています"),
+        [i] => format!("{} 回目の返答が他の返答と矛盾しています", i + 1),
+        ref indexes => format!("{} 回目の返答のどれかが間違っています", indexes.iter().map(|i| i + 1).join(", ")),
+    }
+}
+
+// プレイヤーが考えた答えを、コンピュータが当てる
+fn breaker(args: &Args) -> Result<()> {
+    let rule = args.rule()?;
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut solver = args.solver(&rule, seed)?;
+
+    let mut view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count, rule.duplicate);
+    solver.set_progress(view.progress());
+    view.update_breaker()?;
+
+    let mut histories = Vec::new();
+    while histories.len() < rule.try_count as usize {
+        // 候補が残っていなければ、どこかの返答が間違っている
+        let Some(pins) = solver.next_guess(&histories) else {
+            view.end_message(&histories, &contradiction_message(&rule, &histories));
+            return Ok(());
+        };
+
+        let (hit, blow) = view.wait_feedback(&histories, &pins)?;
+        histories.push(History { pins, hints: hints(hit, blow, rule.answer_count as usize) });
+        if hit == rule.answer_count as usize {
+            view.end_message(&histories, &format!("コンピュータの勝ち！ ({} 回)", histories.len()));
+            return Ok(());
+        }
+    }
+    view.end_message(&histories, "コンピュータの負け");
+
+    Ok(())
+}
+// ボードゲームの補助
+// 実際の盤面での回答とヒントを入力すると、残っている候補と次の回答のおすすめを表示する
+fn assist(args: &Args) -> Result<()> {
+    let rule = args.rule()?;
+    let palette = rule.palette();
+    let codes: Vec<Code> = rule.code_space().iter().collect();
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut solver = args.solver(&rule, seed)?;
+
+    let mut view = ConsoleView::new(&rule.pins.iter().sorted().collect::<Vec<&Pin>>(), rule.answer_count, rule.try_count, rule.duplicate);
+    view.set_confirm(args.confirm);
+    solver.set_progress(view.progress());
+    view.update()?;
+
+    let mut histories = Vec::new();
+    while histories.len() < rule.try_count as usize {
+        let candidates: Vec<Vec<Pin>> = solver::candidates(&codes, &palette, &histories).into_iter().map(|code| code.to_pins(&palette)).collect();
+        if candidates.is_empty() {
+            view.end_message(&histories, &contradiction_message(&rule, &histories));
+            return Ok(());
+        }
+        let recommended = solver.next_guess(&histories);
+        view.view_candidates(&candidates, recommended.as_deref());
+
+        // 盤面の回答を入力してもらう(ヒントキーでおすすめを入力済みにする)
+        let mut prefill = None;
+        let pins = loop {
+            match view.wait_input(&histories, &remaining_counts(&rule, &histories), prefill.as_deref())? {
+                Input::Answer(pins) => break pins,
+                Input::Hint => prefill = recommended.clone().map(|pins| pins.into_iter().map(Some).collect::<Vec<_>>()),
+                Input::TakeBack => (),
+                Input::Escape(_) => return Err(Box::new(Error::EndOfEscape)),
+            }
+        };
+        let (hit, blow) = view.wait_feedback(&histories, &pins)?;
+        histories.push(History { pins, hints: hints(hit, blow, rule.answer_count as usize) });
+        if hit == rule.answer_count as usize {
+            view.end_message(&histories, &format!("正解！ ({} 回)", histories.len()));
+            return Ok(());
+        }
+    }
+    view.end_message(&histories, "最後まで当てられませんでした");
+
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
@@ -145,219 +648,252 @@ mod tests {
 
     #[test]
     fn answer_judge() {
-        let answer = Answer { answer: vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ] };
+        let answer = Answer { answer: vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ], duplicate: false };
 
         // all Hit
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::Hit ]);
 
         // 2 Hit, 2 Blow
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::red(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::red(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::Hit ]);
 
         // 1 Hit, 3 Blow
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::red(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::yellow(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::red(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::green(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::red(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::yellow(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::red(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::green(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Hit ]);
 
         // all Blow
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::red(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::green(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::yellow(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::red(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::red(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::red(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::green(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::yellow(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::red(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::red(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::Blow ]);
 
         // 3 Hit, 1 None
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::orange(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::orange(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::Hit, Hint::None ]);
 
         // 2 Hit, 1 Blow, 1 None
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::orange(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::blue(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::red(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::pink(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::orange(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::blue(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::red(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::pink(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::Hit, Hint::None ]);
 
         // 1 Hit, 2 Blow, 1 None
         //  Hit=Red
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::blue(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::blue(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
         //  Hit=Blue
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::red(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::blue(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::red(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::blue(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
         //  Hit=Green
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::red(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::yellow(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::yellow(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::orange(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::pink(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::orange(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::red(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::red(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::yellow(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::red(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::yellow(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::yellow(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::orange(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::pink(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::orange(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::red(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::red(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::yellow(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
         //  Hit=Yellow
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::red(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::green(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::green(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::pink(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::orange(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::pink(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::red(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::red(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::green(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::red(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::green(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::green(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::pink(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::orange(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::pink(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::red(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::red(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::green(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Hit, Hint::None ]);
 
         // 0 Hit, 3 Blow, 1 None(1st)
         //      RBG
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::red(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::green(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::green(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::red(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::green(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::green(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RBY
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::red(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::yellow(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::yellow(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::red(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::yellow(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::yellow(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RGY
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::red(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::yellow(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::green(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::red(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::yellow(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::green(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      BGY
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::yellow(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::green(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::yellow(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::green(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         // 0 Hit, 3 Blow, 1 None(2nd)
         //      RBG
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::orange(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::pink(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::orange(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::orange(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::pink(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::orange(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RBY
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::pink(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::orange(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::pink(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::pink(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::orange(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::pink(), Pin::blue(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RGY
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::pink(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::orange(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::pink(), Pin::red(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::orange(), Pin::yellow(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      BGY
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::orange(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::pink(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::orange(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::orange(), Pin::yellow(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::pink(), Pin::blue(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::orange(), Pin::yellow(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         // 0 Hit, 3 Blow, 1 None(3rd)
         //      RBG
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::red(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::red(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::green(), Pin::pink(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::red(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::red(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::green(), Pin::pink(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RBY
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::red(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::yellow(), Pin::pink(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::red(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::yellow(), Pin::pink(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RGY
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::red(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::pink(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::red(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::pink(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      BGY
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::yellow(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::pink(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::yellow(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::pink(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         // 0 Hit, 3 Blow, 1 None(4th)
         //      RBG
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::red(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::green(), Pin::red(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::red(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::green(), Pin::red(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RBY
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::red(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::red(), Pin::blue(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::yellow(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::red(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::red(), Pin::blue(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::yellow(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      RGY
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::red(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::red(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::red(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::red(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
         //      BGY
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::green(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::blue(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::green(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::blue(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::Blow, Hint::None ]);
 
         // 2 Hit, 2 None
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::orange(), Pin::pink() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::orange(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::blue(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::pink(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::orange(), Pin::pink() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::orange(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::blue(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::pink(), Pin::green(), Pin::yellow() ]).unwrap(),  vec![ Hint::Hit, Hint::Hit, Hint::None, Hint::None ]);
 
         // 1 Hit, 1 Blow, 2 None
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::orange(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::pink(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::yellow(), Pin::pink(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::red(), Pin::orange(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::blue(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::blue(), Pin::orange(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::blue(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::blue(), Pin::orange(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::blue(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::red(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::orange(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::orange(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::orange(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::orange(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::yellow(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::red(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::orange(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::orange(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::orange(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::orange(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::green(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::orange(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::orange(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::green(), Pin::pink(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::yellow(), Pin::pink(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::red(), Pin::orange(), Pin::yellow(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::blue(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::orange(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::blue(), Pin::orange(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::blue(), Pin::pink(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::blue(), Pin::orange(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::blue(), Pin::yellow(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::red(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::orange(), Pin::green(), Pin::red() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::orange(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::orange(), Pin::green(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::orange(), Pin::green(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::yellow(), Pin::green(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::red(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::orange(), Pin::red(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::orange(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::orange(), Pin::blue(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::orange(), Pin::pink(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::green(), Pin::orange(), Pin::yellow() ]).unwrap(),  vec![ Hint::Blow, Hint::Hit, Hint::None, Hint::None ]);
 
         // 0 Hit, 2 Blow, 2 None
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::pink(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::pink(), Pin::red(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::orange(), Pin::yellow(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::blue(), Pin::pink(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::yellow(), Pin::orange(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
-        assert_eq!(answer.judge(&vec![ Pin::green(), Pin::yellow(), Pin::pink(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::pink(), Pin::red(), Pin::blue() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::pink(), Pin::red(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::orange(), Pin::yellow(), Pin::red(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::blue(), Pin::pink(), Pin::orange(), Pin::green() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::yellow(), Pin::orange(), Pin::blue(), Pin::pink() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
+        assert_eq!(answer.judge(&[ Pin::green(), Pin::yellow(), Pin::pink(), Pin::orange() ]).unwrap(),  vec![ Hint::Blow, Hint::Blow, Hint::None, Hint::None ]);
 
         // less
-        assert!(answer.judge(&vec![ Pin::red() ]).is_none());
-        assert!(answer.judge(&vec![ Pin::red(), Pin::green() ]).is_none());
-        assert!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::blue() ]).is_none());
+        assert!(answer.judge(&[ Pin::red() ]).is_none());
+        assert!(answer.judge(&[ Pin::red(), Pin::green() ]).is_none());
+        assert!(answer.judge(&[ Pin::red(), Pin::green(), Pin::blue() ]).is_none());
 
         // duplicate
-        assert!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::blue(), Pin::red() ]).is_none());
-        assert!(answer.judge(&vec![ Pin::red(), Pin::green(), Pin::green(), Pin::yellow() ]).is_none());
-        assert!(answer.judge(&vec![ Pin::red(), Pin::blue(), Pin::blue(), Pin::yellow() ]).is_none());
-        assert!(answer.judge(&vec![ Pin::yellow(), Pin::green(), Pin::blue(), Pin::yellow() ]).is_none());
-        assert!(answer.judge(&vec![ Pin::red(), Pin::pink(), Pin::orange(), Pin::pink() ]).is_none());
+        assert!(answer.judge(&[ Pin::red(), Pin::green(), Pin::blue(), Pin::red() ]).is_none());
+        assert!(answer.judge(&[ Pin::red(), Pin::green(), Pin::green(), Pin::yellow() ]).is_none());
+        assert!(answer.judge(&[ Pin::red(), Pin::blue(), Pin::blue(), Pin::yellow() ]).is_none());
+        assert!(answer.judge(&[ Pin::yellow(), Pin::green(), Pin::blue(), Pin::yellow() ]).is_none());
+        assert!(answer.judge(&[ Pin::red(), Pin::pink(), Pin::orange(), Pin::pink() ]).is_none());
+    }
+
+    #[test]
+    fn take_back_unranked() {
+        let rule = Rule::default();
+        let answer = [ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let mut histories = histories(&answer, vec![ vec![ Pin::red(), Pin::blue(), Pin::pink(), Pin::orange() ] ]);
+        let mut unranked = false;
+
+        // 取り消す回答がなければ、ランキングに残る
+        take_back(&mut Vec::new(), &mut unranked);
+        assert!(!unranked);
+        assert!(ranked_score(&rule, &histories, true, unranked, Duration::ZERO, 0).is_some());
+
+        // 取り消したら、勝ってもランキングにも成績にも残さない
+        take_back(&mut histories, &mut unranked);
+        assert!(histories.is_empty());
+        assert!(unranked);
+        assert!(ranked_score(&rule, &histories, true, unranked, Duration::ZERO, 0).is_none());
+    }
+
+    #[test]
+    fn history_remaining_counts() {
+        let rule = Rule::default();
+        let answer = vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let histories = histories(&answer, vec![
+            vec![ Pin::pink(), Pin::orange(), Pin::red(), Pin::blue() ],
+            vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ],
+        ]);
+
+        assert_eq!(rule.codes().len(), 360);
+        // 4色のうち2色が Blow: 2色の選び方(6通り) × 残りの green, yellow を含む並び(14通り)
+        assert_eq!(remaining_counts(&rule, &histories), vec![ 84, 1 ]);
     }
 }
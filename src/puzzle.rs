@@ -0,0 +1,280 @@
+use std::fs;
+use std::path::Path;
+
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::{Args, Error, History, Pin, Rule, hints};
+use crate::code::Code;
+use crate::record::{history_line, parse_history};
+use crate::solver;
+
+// 推理パズル: 答えがただ 1 つに決まるヒントつきの回答(手がかり)
+pub struct Puzzle {
+    pub rule: String,   // ルールの名前
+    pub clues: Vec<History>,
+}
+
+// 難しさ
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+
+    pub const ALL: [Difficulty; 4] = [ Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Expert ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Expert => "expert",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|difficulty| difficulty.name() == name)
+    }
+
+    fn from_depth(depth: f64) -> Self {
+        match depth {
+            depth if depth < 0.5 => Difficulty::Easy,
+            depth if depth < 0.875 => Difficulty::Normal,
+            depth if depth < 1.25 => Difficulty::Hard,
+            _ => Difficulty::Expert,
+        }
+    }
+}
+
+// パズルの難しさの評価
+pub struct Grade {
+    pub clues: usize,   // 手がかりの数
+    // 推理の深さ: 候補を一番減らす手がかりから順に使っていったとき、途中で残る候補数の log2 の合計
+    // 弱い手がかりをたくさん組み合わせるほど大きい。ルールによらないよう、すべての答えの数の log2 で割る
+    pub depth: f64,
+    pub difficulty: Difficulty,
+}
+
+impl Puzzle {
+
+    const HEADER: &'static str = "# master-mind puzzle 1";
+
+    pub fn grade(&self, rule: &Rule) -> Grade {
+        let palette = rule.palette();
+        let codes: Vec<Code> = rule.code_space().iter().collect();
+        let mut used: Vec<History> = Vec::new();
+        let mut rest = self.clues.clone();
+        let mut depth = 0.0;
+        while !rest.is_empty() {
+            let (i, count) = rest.iter().enumerate()
+                .map(|(i, clue)| {
+                    used.push(clue.clone());
+                    let count = solver::candidates(&codes, &palette, &used).len();
+                    used.pop();
+                    (i, count)
+                })
+                .min_by_key(|(_, count)| *count)
+                .unwrap();
+            used.push(rest.remove(i));
+            depth += (count.max(1) as f64).log2();
+        }
+        let depth = depth / (codes.len().max(2) as f64).log2();
+        Grade { clues: self.clues.len(), depth, difficulty: Difficulty::from_depth(depth) }
+    }
+
+    // 手がかりと矛盾しない答え
+    pub fn solutions(&self, rule: &Rule) -> Vec<Vec<Pin>> {
+        let palette = rule.palette();
+        let codes: Vec<Code> = rule.code_space().iter().collect();
+        solver::candidates(&codes, &palette, &self.clues).into_iter().map(|code| code.to_pins(&palette)).collect()
+    }
+
+    // 答えがただ 1 つなら、その答え
+    pub fn answer(&self, rule: &Rule) -> Option<Vec<Pin>> {
+        let mut solutions = self.solutions(rule);
+        if solutions.len() != 1 { return None; }
+        solutions.pop()
+    }
+
+    // 答えは書かない(手がかりから決まる)
+    //  # master-mind puzzle 1
+    //  rule default
+    //  1. pink orange red blue 0-2
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![ Self::HEADER.to_string(), format!("rule {}", self.rule) ];
+        lines.extend(self.clues.iter().enumerate().map(|(i, clue)| history_line(i, clue)));
+        lines.join("\n") + "\n"
+    }
+
+    // 見出しの後の "#" で始まる行と空行は読み飛ばす
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        if lines.next()? != Self::HEADER { return None; }
+        let mut lines = lines.filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let rule = lines.next()?.strip_prefix("rule ")?.to_string();
+        let clues = lines.enumerate().map(|(i, line)| parse_history(line, i)).collect::<Option<_>>()?;
+        Some(Self { rule, clues })
+    }
+
+    // いくつものパズルを並べたファイル。それぞれ見出しの行から始まる
+    pub fn parse_all(text: &str) -> Option<Vec<Self>> {
+        let mut texts: Vec<String> = Vec::new();
+        for line in text.lines() {
+            match texts.last_mut() {
+                Some(text) if line != Self::HEADER => { text.push_str(line); text.push('\n'); },
+                _ => texts.push(format!("{}\n", line)),
+            }
+        }
+        texts.iter().map(|text| Self::parse(text)).collect()
+    }
+
+    // ファイルの number 番目 (1 から) のパズルを読む
+    // ルールがあり、手がかりがルールに合っていて、答えがただ 1 つのパズルだけ
+    pub fn load(path: &Path, number: usize) -> crate::Result<(Self, Rule)> {
+        let error = || Error::Puzzle { path: path.display().to_string() };
+        let mut puzzles = Self::parse_all(&fs::read_to_string(path)?).ok_or_else(error)?;
+        if number == 0 || number > puzzles.len() { return Err(Box::new(error())); }
+        let puzzle = puzzles.swap_remove(number - 1);
+        let rule = Rule::preset(&puzzle.rule).ok_or_else(error)?;
+        let is_valid = puzzle.clues.len() < rule.try_count as usize
+            && puzzle.clues.iter().all(|clue| rule.is_code(&clue.pins))
+            && puzzle.answer(&rule).is_some();
+        if !is_valid { return Err(Box::new(error())); }
+        Ok((puzzle, rule))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+}
+
+// 答えを選び、答えがただ 1 つに決まるまでランダムな回答を手がかりに加える
+// 最後に、なくても答えが決まる手がかりを取り除く
+pub fn generate(name: &str, rule: &Rule, rng: &mut StdRng) -> Puzzle {
+    let palette = rule.palette();
+    let codes: Vec<Code> = rule.code_space().iter().collect();
+    let answer = *codes.choose(rng).unwrap();
+    // 回答できる回数のうち、少なくとも 1 回はプレイヤーに残す
+    let max_clues = rule.try_count as usize - 1;
+    loop {
+        let mut clues: Vec<History> = Vec::new();
+        let mut remaining = codes.len();
+        while remaining > 1 && clues.len() < max_clues {
+            let guess = *codes.choose(rng).unwrap();
+            if guess == answer { continue; }
+            let (hit, blow) = guess.hit_blow(answer);
+            clues.push(History { pins: guess.to_pins(&palette), hints: hints(hit, blow, guess.len()) });
+            // 候補が減らない回答は手がかりにならない
+            let count = solver::candidates(&codes, &palette, &clues).len();
+            if count == remaining {
+                clues.pop();
+            }
+            remaining = count;
+        }
+        if remaining > 1 { continue; }
+
+        for i in (0..clues.len()).rev() {
+            let clue = clues.remove(i);
+            if solver::candidates(&codes, &palette, &clues).len() > 1 {
+                clues.insert(i, clue);
+            }
+        }
+        return Puzzle { rule: name.to_string(), clues };
+    }
+}
+
+// 指定の難しさを作ろうとする回数。ルールによっては作れない難しさがある
+const GRADED_ATTEMPTS: usize = 10_000;
+
+// 指定の難しさになるまでパズルを作る
+pub fn generate_graded(name: &str, rule: &Rule, difficulty: Difficulty, rng: &mut StdRng) -> crate::Result<Puzzle> {
+    for _ in 0..GRADED_ATTEMPTS {
+        let puzzle = generate(name, rule, rng);
+        if puzzle.grade(rule).difficulty == difficulty {
+            return Ok(puzzle);
+        }
+    }
+    Err(Box::new(Error::Difficulty { difficulty: difficulty.name().to_string() }))
+}
+
+// 難しさを指定してパズルをまとめて作り、1 つのファイルにする。指定がなければ易しい順にすべての難しさ
+pub fn run_batch(args: &Args) -> crate::Result<()> {
+    let rule = args.rule()?;
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let difficulties = args.difficulty.map_or(Difficulty::ALL.to_vec(), |difficulty| vec![ difficulty ]);
+
+    let mut text = String::new();
+    for difficulty in difficulties {
+        for _ in 0..args.count {
+            let puzzle = generate_graded(&args.rule, &rule, difficulty, &mut rng)?;
+            let grade = puzzle.grade(&rule);
+            let mut lines = puzzle.to_text().lines().map(String::from).collect::<Vec<_>>();
+            lines.insert(1, format!("# difficulty {}  clues {}  depth {:.2}", difficulty.name(), grade.clues, grade.depth));
+            text += &(lines.join("\n") + "\n\n");
+        }
+    }
+    match &args.output {
+        Some(path) => fs::write(path, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn puzzle_generate() {
+        let rule = Rule::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..3 {
+            let puzzle = generate("default", &rule, &mut rng);
+            let answer = puzzle.answer(&rule).unwrap();
+            assert!(puzzle.clues.len() < rule.try_count as usize);
+            assert!(puzzle.clues.iter().all(|clue| clue.pins != answer));
+            // どの手がかりも欠かせない
+            for i in 0..puzzle.clues.len() {
+                let mut clues = puzzle.clues.clone();
+                clues.remove(i);
+                assert!(Puzzle { rule: puzzle.rule.clone(), clues }.solutions(&rule).len() > 1);
+            }
+
+            let parsed = Puzzle::parse(&puzzle.to_text()).unwrap();
+            assert_eq!(parsed.answer(&rule), Some(answer));
+        }
+        assert!(Puzzle::parse("# master-mind puzzle 1\nrule default\n2. red blue green yellow 0-0\n").is_none());
+
+        // 指定した難しさで作れて、まとめたファイルから読める
+        let mut text = String::new();
+        for difficulty in [ Difficulty::Easy, Difficulty::Expert ] {
+            let puzzle = generate_graded("default", &rule, difficulty, &mut rng).unwrap();
+            let grade = puzzle.grade(&rule);
+            assert_eq!(grade.difficulty, difficulty);
+            assert_eq!(grade.clues, puzzle.clues.len());
+            text += &puzzle.to_text().replacen("\n", "\n# comment\n", 1);
+            text += "\n";
+        }
+        let puzzles = Puzzle::parse_all(&text).unwrap();
+        assert_eq!(puzzles.len(), 2);
+        assert!(puzzles[0].grade(&rule).depth < puzzles[1].grade(&rule).depth);
+    }
+
+    #[test]
+    fn puzzle_unreachable_difficulty() {
+        // 答えが 2 つしかないので、手がかり 1 つで決まり、いつも易しい
+        let rule = Rule { pins: HashSet::from([ Pin::red(), Pin::blue() ]), answer_count: 2, try_count: 3, duplicate: false };
+        let mut rng = StdRng::seed_from_u64(1);
+        assert!(generate_graded("tiny", &rule, Difficulty::Easy, &mut rng).is_ok());
+        let error = generate_graded("tiny", &rule, Difficulty::Expert, &mut rng).err().unwrap();
+        assert_eq!(error.to_string(), "could not generate a puzzle of difficulty expert for this rule");
+    }
+}
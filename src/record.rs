@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use itertools::Itertools;
+
+use crate::{Error, Hint, History, Pin, Rule, hints};
+use crate::dirs::data_dir;
+
+// 終わったゲームの記録
+pub struct Record {
+    pub rule: String,   // ルールの名前
+    pub seed: u64,
+    pub date: u64,      // 終わった日時 (UNIX 時間の秒)
+    pub elapsed: Duration,
+    pub hint_count: u32,
+    pub answer: Vec<Pin>,
+    pub histories: Vec<History>,
+}
+
+impl Record {
+
+    const HEADER: &'static str = "# master-mind record 1";
+
+    pub fn new(rule: &str, seed: u64, elapsed: Duration, hint_count: u32, answer: &[Pin], histories: &[History]) -> Self {
+        let date = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |now| now.as_secs());
+        Self { rule: rule.to_string(), seed, date, elapsed, hint_count, answer: answer.to_vec(), histories: histories.to_vec() }
+    }
+
+    // ルールに合っている記録か(盤面に収まらない記録は再生できない)
+    pub fn is_valid(&self, rule: &Rule) -> bool {
+        rule.is_code(&self.answer)
+            && self.histories.iter().all(|history| rule.is_code(&history.pins))
+            && self.histories.len() <= rule.try_count as usize
+    }
+
+    pub fn is_win(&self) -> bool {
+        self.histories.last().is_some_and(|history| history.hints.iter().all(|h| h == &Hint::Hit))
+    }
+
+    // 色を見せずに結果を共有するための文章
+    // シードは書かない(--seed で同じ答えのゲームを作れてしまう)
+    //  Mastermind default 2/10
+    //  🟨🟨⬛⬛
+    //  🟩🟩🟩🟩
+    pub fn share_text(&self, try_count: u32) -> String {
+        let count = if self.is_win() { self.histories.len().to_string() } else { String::from("X") };
+        let mut lines = vec![ format!("Mastermind {} {}/{}", self.rule, count, try_count) ];
+        lines.extend(self.histories.iter().map(|history| {
+            let (hit, blow) = history.hit_blow();
+            "🟩".repeat(hit) + &"🟨".repeat(blow) + &"⬛".repeat(history.pins.len() - hit - blow)
+        }));
+        lines.join("\n")
+    }
+
+    // 見出しの後に、1 行に 1 回答 "番号. 色 色 ... Hit数-Blow数"
+    //  # master-mind record 1
+    //  rule default
+    //  ...
+    //  secret red blue green yellow
+    //  1. pink orange red blue 0-2
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            Self::HEADER.to_string(),
+            format!("rule {}", self.rule),
+            format!("seed {}", self.seed),
+            format!("date {}", self.date),
+            format!("elapsed {}", self.elapsed.as_secs()),
+            format!("hints {}", self.hint_count),
+            format!("secret {}", self.answer.iter().map(|pin| pin.name()).join(" ")),
+        ];
+        lines.extend(self.histories.iter().enumerate().map(|(i, history)| history_line(i, history)));
+        lines.join("\n") + "\n"
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        if lines.next()? != Self::HEADER { return None; }
+        let (mut rule, mut seed, mut date, mut elapsed, mut hint_count, mut answer) = (None, None, None, None, None, None);
+        let mut histories = Vec::new();
+        for line in lines {
+            if line.contains(". ") {
+                histories.push(parse_history(line, histories.len())?);
+                continue;
+            }
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "rule" => rule = Some(value.to_string()),
+                "seed" => seed = Some(value.parse().ok()?),
+                "date" => date = Some(value.parse().ok()?),
+                "elapsed" => elapsed = Some(Duration::from_secs(value.parse().ok()?)),
+                "hints" => hint_count = Some(value.parse().ok()?),
+                "secret" => answer = Some(value.split(' ').map(Pin::from_name).collect::<Option<Vec<Pin>>>()?),
+                _ => return None,
+            }
+        }
+        Some(Self { rule: rule?, seed: seed?, date: date?, elapsed: elapsed?, hint_count: hint_count?, answer: answer?, histories })
+    }
+
+    // 記録のディレクトリに書く。ファイル名は日時から始まるので、名前順が古い順
+    pub fn write(&self) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(dir())?;
+        let path = dir().join(format!("{:012}-{}.txt", self.date, self.seed));
+        fs::write(&path, self.to_text())?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Self::parse(&text).ok_or(Error::Record { path: path.display().to_string() })?)
+    }
+}
+
+// i 番目 (0 から) の回答の行 "番号. 色 色 ... Hit数-Blow数"
+pub fn history_line(i: usize, history: &History) -> String {
+    let (hit, blow) = history.hit_blow();
+    format!("{}. {} {}-{}", i + 1, history.pins.iter().map(|pin| pin.name()).join(" "), hit, blow)
+}
+
+// i 番目 (0 から) の回答の行を読む。番号が違えば None
+pub fn parse_history(line: &str, i: usize) -> Option<History> {
+    let (number, guess) = line.split_once(". ")?;
+    if number.parse::<usize>().ok()? != i + 1 { return None; }
+    let (pins, feedback) = guess.rsplit_once(' ')?;
+    let pins: Vec<Pin> = pins.split(' ').map(Pin::from_name).collect::<Option<_>>()?;
+    let (hit, blow) = feedback.split_once('-')?;
+    let (hit, blow) = (hit.parse().ok()?, blow.parse().ok()?);
+    if hit + blow > pins.len() { return None; }
+    Some(History { hints: hints(hit, blow, pins.len()), pins })
+}
+
+pub fn dir() -> PathBuf {
+    data_dir().join("records")
+}
+
+// すべての記録のファイル (古い順)
+pub fn list() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir()) else { return Vec::new() };
+    entries.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .sorted()
+        .collect()
+}
+
+// UNIX 時間の秒を "2024-01-31 12:34" (UTC) にする
+pub fn date_string(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let (hour, minute) = (secs % 86400 / 3600, secs % 3600 / 60);
+    // 1970-01-01 からの日数を年月日にする (Howard Hinnant の civil_from_days)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, hour, minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::histories;
+
+    #[test]
+    fn record_text() {
+        let answer = vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let histories = histories(&answer, vec![
+            vec![ Pin::pink(), Pin::orange(), Pin::red(), Pin::blue() ],
+            vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ],
+        ]);
+        let record = Record::new("default", 42, Duration::from_secs(83), 1, &answer, &histories);
+        assert!(record.is_win());
+
+        let text = record.to_text();
+        assert!(text.contains("\n1. pink orange red blue 0-2\n2. red blue green yellow 4-0\n"));
+        let parsed = Record::parse(&text).unwrap();
+        assert_eq!(parsed.to_text(), text);
+        assert_eq!(parsed.histories.len(), 2);
+        assert!(parsed.is_win());
+        assert!(Record::parse(&text.replace("2. red", "3. red")).is_none());
+        let rule = Rule::default();
+        assert!(parsed.is_valid(&rule));
+        // 盤面に収まらない回数、足りないピン、ルールにない色は再生できない
+        let long = (0..=rule.try_count as usize).fold(text.clone(), |text, i| text + &history_line(i + 2, &histories[0]) + "\n");
+        assert!(!Record::parse(&long).unwrap().is_valid(&rule));
+        assert!(!Record::parse(&text.replace("1. pink orange red blue 0-2", "1. pink orange red 0-2")).unwrap().is_valid(&rule));
+        assert!(!Record::parse(&text.replace("1. pink orange", "1. white orange")).unwrap().is_valid(&rule));
+        assert_eq!(record.share_text(10), "Mastermind default 2/10\n🟨🟨⬛⬛\n🟩🟩🟩🟩");
+
+        assert_eq!(date_string(0), "1970-01-01 00:00");
+        assert_eq!(date_string(1709210096), "2024-02-29 12:34");
+    }
+}
@@ -0,0 +1,173 @@
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use crate::{Error, Pin, Rule};
+use crate::dirs::data_dir;
+
+// 中断したゲーム。ヒントは答えから計算し直せるので回答だけを持つ
+#[derive(PartialEq, Debug)]
+pub struct SavedGame {
+    pub rule: String,   // ルールの名前
+    pub seed: u64,
+    pub answer: Vec<Pin>,
+    pub guesses: Vec<Vec<Pin>>,
+    pub elapsed: Duration,
+    pub hint_count: u32,
+    pub practice: bool, // 練習のゲーム
+    pub unranked: bool, // 回答を取り消したのでランク外
+}
+
+impl SavedGame {
+
+    const HEADER: &'static str = "# master-mind save 1";
+    // シードを隠す鍵。シードがわかると答えを作り直せるので、ファイルには書かない
+    const SEED_KEY: u64 = 0x7365_6372_6574_2121;
+
+    // 1 行に 1 項目。答えとシードはファイルを開いただけでは読めないようにしておく
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            Self::HEADER.to_string(),
+            format!("rule {}", self.rule),
+            format!("seed {}", obfuscate(&self.seed.to_string(), Self::SEED_KEY)),
+            format!("elapsed {}", self.elapsed.as_secs()),
+            format!("hints {}", self.hint_count),
+            format!("practice {}", self.practice),
+            format!("unranked {}", self.unranked),
+            format!("secret {}", obfuscate(&names(&self.answer), self.seed)),
+        ];
+        lines.extend(self.guesses.iter().map(|pins| format!("guess {}", names(pins))));
+        lines.join("\n") + "\n"
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        if lines.next()? != Self::HEADER { return None; }
+        let (mut rule, mut seed, mut elapsed, mut hint_count, mut secret) = (None, None, None, None, None);
+        // 練習の行がない古いファイルは、練習ではない
+        let (mut practice, mut unranked) = (false, false);
+        let mut guesses = Vec::new();
+        for line in lines {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "rule" => rule = Some(value.to_string()),
+                "seed" => seed = Some(deobfuscate(value, Self::SEED_KEY)?.parse().ok()?),
+                "elapsed" => elapsed = Some(Duration::from_secs(value.parse().ok()?)),
+                "hints" => hint_count = Some(value.parse().ok()?),
+                "practice" => practice = value.parse().ok()?,
+                "unranked" => unranked = value.parse().ok()?,
+                "secret" => secret = Some(value),
+                "guess" => guesses.push(pins(value)?),
+                _ => return None,
+            }
+        }
+        let seed = seed?;
+        let answer = pins(&deobfuscate(secret?, seed)?)?;
+        Some(Self { rule: rule?, seed, answer, guesses, elapsed: elapsed?, hint_count: hint_count?, practice, unranked })
+    }
+
+    // ルールに合っていて、まだ終わっていないゲームか
+    pub fn is_valid(&self, rule: &Rule) -> bool {
+        rule.is_code(&self.answer)
+            && self.guesses.iter().all(|pins| rule.is_code(pins))
+            && self.guesses.len() < rule.try_count as usize
+            && !self.guesses.contains(&self.answer)
+    }
+}
+
+fn names(pins: &[Pin]) -> String {
+    pins.iter().map(|pin| pin.name()).join(" ")
+}
+
+fn pins(names: &str) -> Option<Vec<Pin>> {
+    names.split(' ').map(Pin::from_name).collect()
+}
+
+// シードから作ったバイト列。乱数のクレートが変わっても同じになるよう splitmix64 で作る
+fn keystream(seed: u64) -> impl Iterator<Item = u8> {
+    let mut state = seed ^ 0x6d61_7374_6572_6d69;
+    iter::repeat_with(move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let z = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31)) as u8
+    })
+}
+
+fn obfuscate(text: &str, seed: u64) -> String {
+    text.bytes().zip(keystream(seed)).map(|(b, k)| format!("{:02x}", b ^ k)).collect()
+}
+
+fn deobfuscate(hex: &str, seed: u64) -> Option<String> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() { return None; }
+    let bytes = (0..hex.len()).step_by(2)
+        .zip(keystream(seed))
+        .map(|(i, k)| u8::from_str_radix(&hex[i..i + 2], 16).ok().map(|b| b ^ k))
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+// 中断したゲームは 1 つだけ保存する
+pub fn path() -> PathBuf {
+    data_dir().join("save.txt")
+}
+
+pub fn save(game: &SavedGame) -> io::Result<()> {
+    fs::create_dir_all(data_dir())?;
+    fs::write(path(), game.to_text())
+}
+
+// 続けられるゲームなら、読み込んだら消す(同じところから何度もやり直せないように)
+// 壊れていたり、ルールに合わなかったりすれば消さずにエラー
+pub fn load() -> crate::Result<SavedGame> {
+    let path = path();
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(Box::new(Error::NoSave)),
+        Err(e) => return Err(Box::new(e)),
+    };
+    let game = SavedGame::parse(&text)
+        .filter(|game| Rule::preset(&game.rule).is_some_and(|rule| game.is_valid(&rule)))
+        .ok_or(Error::Save { path: path.display().to_string() })?;
+    fs::remove_file(&path)?;
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_game() {
+        let rule = Rule::default();
+        let game = SavedGame {
+            rule: String::from("default"), seed: 42,
+            answer: vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ],
+            guesses: vec![ vec![ Pin::pink(), Pin::orange(), Pin::red(), Pin::blue() ] ],
+            elapsed: Duration::from_secs(83), hint_count: 1, practice: true, unranked: true,
+        };
+        let text = game.to_text();
+        assert_eq!(SavedGame::parse(&text), Some(game));
+        // 答えもシードもそのままでは読めない
+        assert!(!text.contains("yellow"));
+        assert!(!text.contains("seed 42"));
+        assert!(text.lines().any(|line| line.starts_with("seed ") && line[5..].parse::<u64>().is_err()));
+        let game = SavedGame::parse(&text).unwrap();
+        assert!(game.is_valid(&rule));
+
+        // 別のシードでは答えを戻せない、知らない行があれば読まない
+        let other_seed = format!("seed {}", obfuscate("43", SavedGame::SEED_KEY));
+        let tampered = text.lines().map(|line| if line.starts_with("seed ") { other_seed.as_str() } else { line }).join("\n");
+        assert!(SavedGame::parse(&tampered).is_none_or(|other| other.seed == 43 && other.answer != game.answer));
+        assert!(SavedGame::parse(&(text.clone() + "cheat 1\n")).is_none());
+        let old = text.replace("practice true\n", "").replace("unranked true\n", "");
+        assert!(SavedGame::parse(&old).is_some_and(|old| !old.practice && !old.unranked));
+        // 終わったゲームは続けられない
+        let finished = SavedGame { guesses: vec![ game.answer.clone() ], ..game };
+        assert!(!finished.is_valid(&rule));
+    }
+}
@@ -0,0 +1,399 @@
+use std::cmp::Ordering;
+use std::sync::atomic::{self, AtomicUsize};
+use std::thread;
+use std::time::Duration;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::{Pin, Rule, History};
+use crate::book::Book;
+use crate::code::Code;
+use crate::table::FeedbackTable;
+
+// 履歴を (回答, ヒントの番号) にする。palette にない色があれば None
+fn pack(histories: &[History], palette: &[Pin]) -> Option<Vec<(Code, usize)>> {
+    histories.iter()
+        .map(|history| {
+            let guess = Code::from_pins(&history.pins, palette)?;
+            let (hit, blow) = history.hit_blow();
+            Some((guess, hit * (guess.len() + 1) + blow))
+        })
+        .collect()
+}
+
+// codes のうち、これまでのヒントすべてと矛盾しない答え
+pub fn candidates(codes: &[Code], palette: &[Pin], histories: &[History]) -> Vec<Code> {
+    let Some(histories) = pack(histories, palette) else { return Vec::new() };
+    codes.iter()
+        .filter(|code| histories.iter().all(|(guess, feedback)| guess.feedback(**code) == *feedback))
+        .copied()
+        .collect()
+}
+
+// 矛盾しているヒントを探す
+// その1つを除けば矛盾しなくなる履歴の番号を返す(2つ以上除かないといけなければ空)
+pub fn contradictions(codes: &[Code], palette: &[Pin], histories: &[History]) -> Vec<usize> {
+    let Some(histories) = pack(histories, palette) else { return Vec::new() };
+    (0..histories.len())
+        .filter(|i| codes.iter().any(|code|
+            histories.iter().enumerate().all(|(j, (guess, feedback))| j == *i || guess.feedback(*code) == *feedback)))
+        .collect()
+}
+
+// guess を回答したとき、候補がヒントごとにいくつに分かれるか
+pub fn partition(guess: Code, candidates: &[Code]) -> Vec<usize> {
+    let mut sizes = vec![0; (guess.len() + 1) * (guess.len() + 1)];
+    for candidate in candidates {
+        sizes[guess.feedback(*candidate)] += 1;
+    }
+    sizes
+}
+
+pub trait Solver {
+    // 次の回答。ヒントに矛盾があり候補が残っていなければ None
+    fn next_guess(&mut self, histories: &[History]) -> Option<Vec<Pin>>;
+
+    // 回答を探すのに使うスレッド数
+    fn set_threads(&mut self, _threads: usize) {}
+
+    // 回答を探している間、(調べ終わった数, 全体の数) を知らせる。終わったら (全体の数, 全体の数)
+    fn set_progress(&mut self, _progress: Box<dyn FnMut(usize, usize)>) {}
+
+    // ヒントを計算する代わりに使う表
+    fn set_table(&mut self, _table: FeedbackTable) {}
+}
+
+// 選べる戦略の名前
+pub const NAMES: [&str; 5] = [ "minimax", "entropy", "expected", "most-parts", "random" ];
+
+pub fn by_name(name: &str, rule: &Rule, seed: u64) -> Option<Box<dyn Solver>> {
+    let solver: Box<dyn Solver> = match name {
+        "minimax" => Box::new(PartitionSolver::new(rule, Strategy::Minimax)),
+        "entropy" => Box::new(PartitionSolver::new(rule, Strategy::Entropy)),
+        "expected" => Box::new(PartitionSolver::new(rule, Strategy::ExpectedSize)),
+        "most-parts" => Box::new(PartitionSolver::new(rule, Strategy::MostParts)),
+        "random" => Box::new(RandomConsistent::new(rule, seed)),
+        _ => return None,
+    };
+    Some(solver)
+}
+
+// 候補の分かれ方による回答の評価方法
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Strategy {
+    Minimax,        // 最悪の場合に残る候補数 (Knuth)
+    Entropy,        // 得られる情報量
+    ExpectedSize,   // 残る候補数の期待値
+    MostParts,      // 分かれる数
+}
+
+impl Strategy {
+
+    pub const ALL: [Strategy; 4] = [ Strategy::Minimax, Strategy::Entropy, Strategy::ExpectedSize, Strategy::MostParts ];
+
+    // by_name で選ぶときの名前
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Minimax => "minimax",
+            Strategy::Entropy => "entropy",
+            Strategy::ExpectedSize => "expected",
+            Strategy::MostParts => "most-parts",
+        }
+    }
+
+    // 小さいほど良い回答
+    fn score(&self, sizes: &[usize], total: usize) -> f64 {
+        let parts = sizes.iter().filter(|size| **size > 0);
+        match self {
+            Strategy::Minimax => parts.max().copied().unwrap_or(0) as f64,
+            Strategy::Entropy => parts.map(|size| {
+                    let p = *size as f64 / total as f64;
+                    p * p.log2()
+                }).sum(),
+            Strategy::ExpectedSize => parts.map(|size| (size * size) as f64).sum::<f64>() / total as f64,
+            Strategy::MostParts => -(parts.count() as f64),
+        }
+    }
+}
+
+// すべての回答について候補の分かれ方を調べ、一番評価の良いものを選ぶ
+// 評価が同じなら候補の中から、さらに同じなら先に列挙されたもの
+// 回答は複数のスレッドで手分けして調べる(スレッド数によらず同じ回答になる)
+pub struct PartitionSolver {
+    palette: Vec<Pin>,
+    codes: Vec<Code>,
+    strategy: Strategy,
+    threads: usize,
+    progress: Option<Box<dyn FnMut(usize, usize)>>,
+    table: Option<FeedbackTable>,
+    book: Option<Book>,         // ルールに合う定跡があれば、最初の 2 回はそれを使う
+    first: Option<Vec<Pin>>,    // 最初の回答はいつも同じなので覚えておく
+}
+
+// (評価, 候補でない, 回答の番号) の小さいものほど良い回答
+type Rank = (f64, bool, usize);
+
+fn compare(a: &Rank, b: &Rank) -> Ordering {
+    a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2))
+}
+
+// ヒントの種類の最大数 ((Hit 数 + 1) * (Blow 数 + 1))
+const FEEDBACKS: usize = (Code::MAX_LEN + 1) * (Code::MAX_LEN + 1);
+
+// 評価をメモリ確保なしで行うため、分かれ方は固定長の配列に数える
+// 回答も候補も codes の番号で、表があれば表から、なければ計算してヒントを求める
+fn code_partition(guess: usize, candidates: &[usize], codes: &[Code], table: Option<&FeedbackTable>) -> [usize; FEEDBACKS] {
+    let mut sizes = [0; FEEDBACKS];
+    match table {
+        Some(table) => candidates.iter().for_each(|candidate| sizes[table.get(guess, *candidate)] += 1),
+        None => candidates.iter().for_each(|candidate| sizes[codes[guess].feedback(codes[*candidate])] += 1),
+    }
+    sizes
+}
+
+impl PartitionSolver {
+
+    // 1 スレッドが一度に受け持つ回答の数
+    const BLOCK: usize = 64;
+
+    pub fn new(rule: &Rule, strategy: Strategy) -> Self {
+        let palette = rule.palette();
+        let codes = rule.code_space().iter().collect();
+        let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let book = Book::find(rule, strategy);
+        Self { palette, codes, strategy, threads, progress: None, table: None, book, first: None }
+    }
+
+    pub fn set_book(&mut self, book: Option<Book>) {
+        self.book = book;
+    }
+
+    // 定跡にある回答 (最初の回答と、定跡どおりの最初の回答の後の 2 回目)
+    fn book_guess(&self, histories: &[History]) -> Option<Vec<Pin>> {
+        let book = self.book.as_ref()?;
+        let index = match histories {
+            [] => book.first(),
+            [ history ] => {
+                let first = self.codes.get(book.first())?;
+                if Code::from_pins(&history.pins, &self.palette)? != *first { return None; }
+                let (hit, blow) = history.hit_blow();
+                book.second(hit, blow)?
+            },
+            _ => return None,
+        };
+        self.codes.get(index).map(|code| code.to_pins(&self.palette))
+    }
+
+    fn best_guess(&mut self, histories: &[History]) -> Option<Vec<Pin>> {
+        let histories = pack(histories, &self.palette)?;
+        let candidates: Vec<usize> = (0..self.codes.len())
+            .filter(|index| histories.iter().all(|(guess, feedback)| guess.feedback(self.codes[*index]) == *feedback))
+            .collect();
+        if candidates.len() <= 2 {
+            return candidates.first().map(|index| self.codes[*index].to_pins(&self.palette));
+        }
+
+        let codes = &self.codes;
+        let table = self.table.as_ref();
+        let strategy = self.strategy;
+        let candidates = &candidates;
+        let next = AtomicUsize::new(0);
+        let done = AtomicUsize::new(0);
+        let best = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads)
+                .map(|_| scope.spawn(|| {
+                    let mut best: Option<Rank> = None;
+                    loop {
+                        let start = next.fetch_add(Self::BLOCK, atomic::Ordering::Relaxed);
+                        if start >= codes.len() { return best; }
+                        let end = (start + Self::BLOCK).min(codes.len());
+                        for index in start..end {
+                            let score = strategy.score(&code_partition(index, candidates, codes, table), candidates.len());
+                            let is_candidate = candidates.binary_search(&index).is_ok();
+                            let rank = (score, !is_candidate, index);
+                            if best.is_none_or(|best| compare(&rank, &best).is_lt()) {
+                                best = Some(rank);
+                            }
+                        }
+                        done.fetch_add(end - start, atomic::Ordering::Relaxed);
+                    }
+                }))
+                .collect();
+
+            // 終わるまで進み具合を知らせ、最後に終わったことを知らせる
+            if let Some(progress) = self.progress.as_mut() {
+                while !workers.iter().all(|worker| worker.is_finished()) {
+                    thread::sleep(Duration::from_millis(50));
+                    progress(done.load(atomic::Ordering::Relaxed), codes.len());
+                }
+                progress(codes.len(), codes.len());
+            }
+            workers.into_iter().filter_map(|worker| worker.join().unwrap()).min_by(compare)
+        });
+
+        best.map(|(_, _, index)| self.codes[index].to_pins(&self.palette))
+    }
+}
+
+impl Solver for PartitionSolver {
+
+    fn next_guess(&mut self, histories: &[History]) -> Option<Vec<Pin>> {
+        if let Some(guess) = self.book_guess(histories) {
+            return Some(guess);
+        }
+        if histories.is_empty() && self.first.is_some() {
+            return self.first.clone();
+        }
+        let guess = self.best_guess(histories);
+        if histories.is_empty() {
+            self.first = guess.clone();
+        }
+        guess
+    }
+
+    fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    fn set_progress(&mut self, progress: Box<dyn FnMut(usize, usize)>) {
+        self.progress = Some(progress);
+    }
+
+    fn set_table(&mut self, table: FeedbackTable) {
+        self.table = Some(table);
+    }
+}
+
+// 矛盾しない候補からランダムに選ぶ
+pub struct RandomConsistent {
+    palette: Vec<Pin>,
+    codes: Vec<Code>,
+    rng: StdRng,
+}
+
+impl RandomConsistent {
+
+    pub fn new(rule: &Rule, seed: u64) -> Self {
+        Self { palette: rule.palette(), codes: rule.code_space().iter().collect(), rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Solver for RandomConsistent {
+
+    fn next_guess(&mut self, histories: &[History]) -> Option<Vec<Pin>> {
+        candidates(&self.codes, &self.palette, histories).choose(&mut self.rng).map(|code| code.to_pins(&self.palette))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use itertools::Itertools;
+    use crate::{Answer, histories, judge_pins};
+
+    // 最初の回答は毎回同じなので、計算済みのものを使う
+    fn play(solver: &mut dyn Solver, first: &[Pin], answer: &[Pin]) -> usize {
+        let mut histories: Vec<History> = Vec::new();
+        loop {
+            let pins = if histories.is_empty() { first.to_vec() } else { solver.next_guess(&histories).unwrap() };
+            let hints = judge_pins(answer, &pins);
+            let is_win = pins == answer;
+            histories.push(History { pins, hints });
+            if is_win { return histories.len(); }
+        }
+    }
+
+    #[test]
+    fn minimax_classic() {
+        let pins = HashSet::from( [ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow(), Pin::pink(), Pin::orange() ] );
+        let rule = Rule { pins, answer_count: 4, try_count: 10, duplicate: true };
+        let mut solver = PartitionSolver::new(&rule, Strategy::Minimax);
+
+        // 最初の回答は Knuth と同じ 1122 の形
+        let first = solver.next_guess(&[]).unwrap();
+        assert_eq!(first[0], first[1]);
+        assert_eq!(first[2], first[3]);
+        assert_ne!(first[1], first[2]);
+
+        for answer in rule.codes().iter().step_by(97) {
+            assert!(play(&mut solver, &first, answer) <= 5);
+        }
+    }
+
+    #[test]
+    fn minimax_no_duplicate() {
+        let pins = HashSet::from( [ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow(), Pin::pink(), Pin::orange() ] );
+        let rule = Rule { pins, answer_count: 4, try_count: 10, duplicate: false };
+        let mut solver = PartitionSolver::new(&rule, Strategy::Minimax);
+
+        let first = solver.next_guess(&[]).unwrap();
+        for answer in rule.codes().iter().step_by(31) {
+            // ゲームの判定と同じ結果になる
+            assert_eq!(Answer { answer: answer.clone(), duplicate: false }.judge(&first).unwrap(), judge_pins(answer, &first));
+            assert!(play(&mut solver, &first, answer) <= 6);
+        }
+    }
+
+    #[test]
+    fn solver_contradictions() {
+        let rule = Rule::default();
+        let codes: Vec<Code> = rule.code_space().iter().collect();
+        let palette = rule.palette();
+        let answer = vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let mut histories = histories(&answer, vec![
+            vec![ Pin::red(), Pin::green(), Pin::blue(), Pin::pink() ],
+            vec![ Pin::orange(), Pin::blue(), Pin::green(), Pin::red() ],
+            vec![ Pin::red(), Pin::blue(), Pin::yellow(), Pin::orange() ],
+        ]);
+        let remaining = candidates(&codes, &palette, &histories);
+        assert!(remaining.contains(&Code::from_pins(&answer, &palette).unwrap()));
+        assert_eq!(remaining.len(), codes.iter().filter(|code| histories.iter().all(|history| history.is_consistent(&code.to_pins(&palette)))).count());
+
+        // 2 回目の返答を間違える(Hit 2, Blow 1 を Hit 0, Blow 0)
+        histories[1].hints = crate::hints(0, 0, 4);
+        assert!(candidates(&codes, &palette, &histories).is_empty());
+        assert_eq!(contradictions(&codes, &palette, &histories), vec![ 1 ]);
+    }
+
+    #[test]
+    fn solver_threads() {
+        let rule = Rule::default();
+        let answer = vec![ Pin::red(), Pin::blue(), Pin::green(), Pin::yellow() ];
+        let histories = vec![ History { hints: judge_pins(&answer, &rule.codes()[0]), pins: rule.codes()[0].clone() } ];
+
+        // スレッド数が違っても、表を使っても同じ回答になる
+        for strategy in Strategy::ALL {
+            let guesses: Vec<_> = [ (1, false), (3, false), (8, false), (3, true) ].into_iter()
+                .map(|(threads, table)| {
+                    let mut solver = PartitionSolver::new(&rule, strategy);
+                    solver.set_book(None);
+                    solver.set_threads(threads);
+                    if table {
+                        solver.set_table(FeedbackTable::new(&rule).unwrap());
+                    }
+                    solver.next_guess(&histories).unwrap()
+                })
+                .collect();
+            assert!(guesses.iter().all_equal());
+        }
+    }
+
+    #[test]
+    fn solver_by_name() {
+        let pins = HashSet::from( [ Pin::red(), Pin::green(), Pin::blue(), Pin::yellow() ] );
+        let rule = Rule { pins, answer_count: 3, try_count: 10, duplicate: true };
+        assert!(by_name("unknown", &rule, 0).is_none());
+
+        // どの戦略でもすべての答えを当てられる
+        for name in NAMES {
+            let mut solver = by_name(name, &rule, 0).unwrap();
+            let first = solver.next_guess(&[]).unwrap();
+            for answer in rule.codes() {
+                assert!(play(solver.as_mut(), &first, &answer) <= rule.codes().len());
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::Error;
+use crate::dirs::data_dir;
+
+// 終わったゲーム 1 つの結果
+#[derive(PartialEq, Debug)]
+pub struct GameResult {
+    pub date: u64,      // 終わった日時 (UNIX 時間の秒)
+    pub rule: String,   // ルールの名前
+    pub win: bool,
+    pub guesses: usize, // 回答した回数
+    pub elapsed: Duration,
+    pub hint_count: u32,
+}
+
+impl GameResult {
+
+    const HEADER: &'static str = "date,rule,result,guesses,seconds,hints";
+
+    fn to_line(&self) -> String {
+        format!("{},{},{},{},{},{}", self.date, self.rule, if self.win { "win" } else { "lose" },
+            self.guesses, self.elapsed.as_secs(), self.hint_count)
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [ date, rule, result, guesses, seconds, hints ] = fields[..] else { return None };
+        let win = match result {
+            "win" => true,
+            "lose" => false,
+            _ => return None,
+        };
+        Some(Self {
+            date: date.parse().ok()?, rule: rule.to_string(), win, guesses: guesses.parse().ok()?,
+            elapsed: Duration::from_secs(seconds.parse().ok()?), hint_count: hints.parse().ok()?,
+        })
+    }
+}
+
+// すべてのゲームの結果を 1 行ずつ追記していくファイル (CSV)
+pub fn path() -> PathBuf {
+    data_dir().join("stats.csv")
+}
+
+pub fn append(result: &GameResult) -> io::Result<()> {
+    fs::create_dir_all(data_dir())?;
+    let path = path();
+    let is_new = !path.exists();
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    if is_new {
+        writeln!(file, "{}", GameResult::HEADER)?;
+    }
+    writeln!(file, "{}", result.to_line())
+}
+
+// 古い順の結果。まだファイルがなければ空
+pub fn load() -> crate::Result<Vec<GameResult>> {
+    let path = path();
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Box::new(e)),
+    };
+    let error = || Error::Stats { path: path.display().to_string() };
+    let mut lines = text.lines();
+    if lines.next() != Some(GameResult::HEADER) { return Err(Box::new(error())); }
+    Ok(lines.map(GameResult::parse).collect::<Option<_>>().ok_or_else(error)?)
+}
+
+// ルールごとの集計
+pub struct Stats {
+    pub games: usize,
+    pub wins: usize,
+    pub streak: usize,      // 今の連勝数
+    pub best_streak: usize, // 最高の連勝数
+    pub histogram: Vec<usize>,  // 当てた回数ごとの勝ったゲーム数 (histogram[0] が 1 回)
+}
+
+impl Stats {
+
+    pub fn new(results: &[GameResult], rule: &str, try_count: u32) -> Self {
+        let mut stats = Stats { games: 0, wins: 0, streak: 0, best_streak: 0, histogram: vec![0; try_count as usize] };
+        for result in results.iter().filter(|result| result.rule == rule) {
+            stats.games += 1;
+            if result.win {
+                stats.wins += 1;
+                stats.streak += 1;
+                stats.best_streak = stats.best_streak.max(stats.streak);
+                if let Some(count) = stats.histogram.get_mut(result.guesses.wrapping_sub(1)) {
+                    *count += 1;
+                }
+            } else {
+                stats.streak = 0;
+            }
+        }
+        stats
+    }
+
+    // 勝率 (%)
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 { return 0.0; }
+        self.wins as f64 * 100.0 / self.games as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_streak() {
+        let result = |rule: &str, win: bool, guesses: usize| GameResult {
+            date: 0, rule: rule.to_string(), win, guesses, elapsed: Duration::from_secs(60), hint_count: 0,
+        };
+        let results = vec![
+            result("default", true, 5), result("default", true, 4), result("classic", false, 10),
+            result("default", true, 5), result("default", false, 10), result("default", true, 6),
+        ];
+        for result in &results {
+            assert_eq!(GameResult::parse(&result.to_line()).as_ref(), Some(result));
+        }
+        assert!(GameResult::parse("0,default,draw,1,1,1").is_none());
+
+        let stats = Stats::new(&results, "default", 10);
+        assert_eq!((stats.games, stats.wins, stats.streak, stats.best_streak), (5, 4, 1, 3));
+        assert_eq!(stats.histogram[3..6], [ 1, 2, 1 ]);
+        assert!((stats.win_rate() - 80.0).abs() < 1e-9);
+
+        let stats = Stats::new(&results, "classic", 10);
+        assert_eq!((stats.games, stats.wins, stats.streak), (1, 0, 0));
+        assert_eq!(Stats::new(&results, "super", 12).win_rate(), 0.0);
+    }
+}
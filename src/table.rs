@@ -0,0 +1,115 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::Rule;
+use crate::code::Code;
+use crate::dirs::cache_dir;
+
+// ルールのすべての (回答, 答え) の組み合わせのヒントの番号を並べたもの
+// 回答と答えは CodeSpace の番号。ヒントの番号は Code::feedback
+pub struct FeedbackTable {
+    count: usize,
+    table: Vec<u8>,
+}
+
+impl FeedbackTable {
+
+    // ファイルの形式が変わったら上げる
+    const VERSION: u32 = 1;
+    const MAGIC: &'static [u8; 4] = b"MMFT";
+    // これより大きくなるルールでは表を作らない(super は 32768 * 32768 で 1GiB)
+    const MAX_SIZE: usize = 64 * 1024 * 1024;
+
+    pub fn new(rule: &Rule) -> Option<Self> {
+        let space = rule.code_space();
+        let count = space.count();
+        let len = rule.answer_count as usize;
+        if count.checked_mul(count).is_none_or(|size| size > Self::MAX_SIZE) || (len + 1) * (len + 1) > 256 {
+            return None;
+        }
+
+        let codes: Vec<Code> = space.iter().collect();
+        let table = codes.iter()
+            .flat_map(|guess| codes.iter().map(|answer| guess.feedback(*answer) as u8))
+            .collect();
+        Some(Self { count, table })
+    }
+
+    pub fn get(&self, guess: usize, answer: usize) -> usize {
+        self.table[guess * self.count + answer] as usize
+    }
+
+    // キャッシュがあれば読み込み、なければ作って保存する
+    pub fn load_or_new(rule: &Rule) -> Option<Self> {
+        let path = Self::cache_path(rule);
+        if let Ok(table) = Self::load(&path, rule) {
+            return Some(table);
+        }
+        let table = Self::new(rule)?;
+        // 保存できなくても表は使える
+        let _ = fs::create_dir_all(cache_dir()).and_then(|_| table.save(&path, rule));
+        Some(table)
+    }
+
+    fn cache_path(rule: &Rule) -> PathBuf {
+        cache_dir().join(format!("feedback-v{}-{}.bin", Self::VERSION, rule.code_space().key()))
+    }
+
+    // ファイルの先頭に書いておく、どのルールの表か
+    fn header(&self, rule: &Rule) -> Vec<u8> {
+        let mut header = Self::MAGIC.to_vec();
+        header.extend(Self::VERSION.to_le_bytes());
+        header.extend([ rule.pins.len() as u8, rule.answer_count as u8, rule.duplicate as u8 ]);
+        header.extend((self.count as u64).to_le_bytes());
+        header
+    }
+
+    pub fn save(&self, path: &Path, rule: &Rule) -> std::io::Result<()> {
+        let mut file = fs::File::create(path)?;
+        file.write_all(&self.header(rule))?;
+        file.write_all(&self.table)
+    }
+
+    pub fn load(path: &Path, rule: &Rule) -> crate::Result<Self> {
+        let count = rule.code_space().count();
+        let mut table = Self { count, table: Vec::new() };
+        let header = table.header(rule);
+
+        let mut data = Vec::new();
+        fs::File::open(path)?.read_to_end(&mut data)?;
+        if !data.starts_with(&header) || data.len() != header.len() + count * count {
+            return Err(Box::new(crate::Error::Cache { path: path.display().to_string() }));
+        }
+        data.drain(..header.len());
+        table.table = data;
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn feedback_table() {
+        let rule = Rule::preset("classic").unwrap();
+        let codes: Vec<Code> = rule.code_space().iter().collect();
+        let table = FeedbackTable::new(&rule).unwrap();
+        for (g, guess) in codes.iter().enumerate().step_by(11) {
+            for (a, answer) in codes.iter().enumerate() {
+                assert_eq!(table.get(g, a), guess.feedback(*answer));
+            }
+        }
+        assert!(FeedbackTable::new(&Rule::preset("super").unwrap()).is_none());
+
+        // 保存して読み込むと同じ表。違うルールでは読み込めない
+        let path = env::temp_dir().join(format!("master-mind-test-{}.bin", std::process::id()));
+        table.save(&path, &rule).unwrap();
+        let loaded = FeedbackTable::load(&path, &rule).unwrap();
+        assert_eq!(loaded.table, table.table);
+        assert!(FeedbackTable::load(&path, &Rule::default()).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
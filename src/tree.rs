@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use itertools::Itertools;
+use rand::{thread_rng, Rng};
+
+use crate::{Args, Error, History, Pin, hints};
+use crate::code::Code;
+use crate::solver::Solver;
+
+// 出力できる形式
+pub const FORMATS: [&str; 2] = [ "json", "dot" ];
+
+// 決定木の節。ソルバーの回答と、返ってくるヒント (Hit 数, Blow 数) ごとの次の節
+pub struct Node {
+    pub guess: Vec<Pin>,
+    pub candidates: usize,  // この回答をするときに残っている候補の数
+    pub is_answer: bool,    // 回答が候補の1つで、当たって終わることがある
+    pub next: BTreeMap<(usize, usize), Node>,
+}
+
+impl Node {
+
+    // 最悪の場合に当てるまでの回数
+    pub fn depth(&self) -> usize {
+        1 + self.next.values().map(Node::depth).max().unwrap_or(0)
+    }
+
+    fn pins(&self) -> String {
+        self.guess.iter().map(|pin| pin.name()).join(" ")
+    }
+}
+
+// histories の後、ソルバーが candidates をすべて当てるまでの木
+pub fn build(solver: &mut dyn Solver, palette: &[Pin], candidates: &[Code], histories: &mut Vec<History>) -> Option<Node> {
+    let guess = solver.next_guess(histories)?;
+    let guess_code = Code::from_pins(&guess, palette)?;
+    let len = guess.len();
+    let mut parts: BTreeMap<(usize, usize), Vec<Code>> = BTreeMap::new();
+    for candidate in candidates {
+        parts.entry(guess_code.hit_blow(*candidate)).or_default().push(*candidate);
+    }
+    let is_answer = parts.remove(&(len, 0)).is_some();
+
+    let mut next = BTreeMap::new();
+    for ((hit, blow), part) in parts {
+        // 候補を絞れない回答が続くと終わらない
+        if part.len() == candidates.len() { return None; }
+        histories.push(History { pins: guess.clone(), hints: hints(hit, blow, len) });
+        let node = build(solver, palette, &part, histories);
+        histories.pop();
+        next.insert((hit, blow), node?);
+    }
+    Some(Node { guess, candidates: candidates.len(), is_answer, next })
+}
+
+fn json(node: &Node, indent: usize, out: &mut String) {
+    let pad = "  ".repeat(indent + 1);
+    out.push_str("{\n");
+    out.push_str(&format!("{}\"guess\": [{}],\n", pad, node.guess.iter().map(|pin| format!("\"{}\"", pin.name())).join(", ")));
+    out.push_str(&format!("{}\"candidates\": {},\n", pad, node.candidates));
+    out.push_str(&format!("{}\"answer\": {},\n", pad, node.is_answer));
+    out.push_str(&format!("{}\"next\": {{", pad));
+    for (i, ((hit, blow), child)) in node.next.iter().enumerate() {
+        out.push_str(if i == 0 { "\n" } else { ",\n" });
+        out.push_str(&format!("{}  \"{}-{}\": ", pad, hit, blow));
+        json(child, indent + 2, out);
+    }
+    if !node.next.is_empty() {
+        out.push_str(&format!("\n{}", pad));
+    }
+    out.push_str("}\n");
+    out.push_str(&format!("{}}}", "  ".repeat(indent)));
+}
+
+// 節には n0, n1, ... と通し番号をつける。当たって終わることがある節は二重枠
+fn dot(node: &Node, id: &mut usize, out: &mut String) -> usize {
+    let this = *id;
+    *id += 1;
+    out.push_str(&format!("  n{} [label=\"{}\\n({})\"{}];\n", this, node.pins(), node.candidates,
+        if node.is_answer { ", peripheries=2" } else { "" }));
+    for ((hit, blow), child) in &node.next {
+        let child = dot(child, id, out);
+        out.push_str(&format!("  n{} -> n{} [label=\"{}-{}\"];\n", this, child, hit, blow));
+    }
+    this
+}
+
+// ソルバーにすべての答えを当てさせ、その決定木を出力する
+pub fn run(args: &Args) -> crate::Result<()> {
+    let format = args.format.as_deref().unwrap_or("json");
+    if !FORMATS.contains(&format) {
+        return Err(Box::new(Error::Args { arg: format.to_string() }));
+    }
+    let rule = args.rule()?;
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let mut solver = args.solver(&rule, seed)?;
+    let codes: Vec<Code> = rule.code_space().iter().collect();
+    let tree = build(solver.as_mut(), &rule.palette(), &codes, &mut Vec::new()).ok_or(Error::NoCandidate)?;
+
+    let mut out = String::new();
+    if format == "dot" {
+        out.push_str("digraph tree {\n");
+        out.push_str(&format!("  label=\"rule: {}  solver: {}  depth: {}\";\n", args.rule, args.solver, tree.depth()));
+        out.push_str("  node [shape=box];\n");
+        dot(&tree, &mut 0, &mut out);
+        out.push('}');
+    } else {
+        out.push_str(&format!("{{\n  \"rule\": \"{}\",\n  \"solver\": \"{}\",\n  \"depth\": {},\n  \"tree\": ", args.rule, args.solver, tree.depth()));
+        json(&tree, 1, &mut out);
+        out.push_str("\n}");
+    }
+    out.push('\n');
+    match &args.output {
+        Some(path) => fs::write(path, out)?,
+        None => print!("{}", out),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rule;
+    use crate::solver::{PartitionSolver, Strategy};
+
+    // 節で当たる答えと、子の節に残る候補で、すべての候補になる
+    fn count(node: &Node) -> usize {
+        let counted = node.is_answer as usize + node.next.values().map(count).sum::<usize>();
+        assert_eq!(counted, node.candidates);
+        counted
+    }
+
+    #[test]
+    fn tree_default() {
+        let rule = Rule::default();
+        let codes: Vec<Code> = rule.code_space().iter().collect();
+        let mut solver = PartitionSolver::new(&rule, Strategy::Minimax);
+        let tree = build(&mut solver, &rule.palette(), &codes, &mut Vec::new()).unwrap();
+
+        assert_eq!(count(&tree), 360);
+        assert!(tree.depth() <= 6);
+
+        let mut out = String::new();
+        json(&tree, 0, &mut out);
+        assert_eq!(out.matches("\"guess\"").count(), out.matches("\"answer\"").count());
+        assert_eq!(out.matches("\"answer\": true").count(), 360);
+        let mut out = String::new();
+        let mut id = 0;
+        dot(&tree, &mut id, &mut out);
+        assert_eq!(out.matches(" -> ").count(), id - 1);
+    }
+}